@@ -0,0 +1,618 @@
+//! Pure bonding-curve math for MarsCorp business tokens.
+//!
+//! Everything here is integer-only and free of Solana dependencies so the same
+//! code prices trades on-chain and in off-chain tools.
+
+use std::fmt;
+
+// ═══════════════════════════════════════════════════════════════════════
+// CONSTANTS
+// ═══════════════════════════════════════════════════════════════════════
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// 30 SOL initial virtual liquidity.
+pub const INITIAL_VIRTUAL_SOL: u128 = 30_000_000_000;
+/// ~1B tokens of initial virtual supply.
+pub const INITIAL_VIRTUAL_TOKENS: u128 = 1_073_000_000_000_000;
+/// 800M tokens minted to the curve for trading.
+pub const CURVE_SUPPLY: u64 = 800_000_000_000_000;
+/// 200M tokens minted to the creator's vesting vault.
+pub const CREATOR_ALLOCATION: u64 = 200_000_000_000_000;
+/// A curve graduates once its real SOL reserves reach this amount. Buys are
+/// capped so they never push reserves past it.
+pub const GRADUATION_THRESHOLD: u64 = 85_000_000_000;
+/// Base units per whole business token (6 decimals).
+pub const TOKEN_UNIT: u128 = 1_000_000;
+/// Fixed-point scale of reward-per-token indices.
+pub const REWARD_INDEX_SCALE: u128 = 1_000_000_000_000;
+/// Period over which yield that arrived with nothing staked is paid out.
+pub const YIELD_STREAM_SECS: i64 = 7 * 86_400;
+
+// ═══════════════════════════════════════════════════════════════════════
+// TYPES
+// ═══════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    InvalidInput,
+    InsufficientLiquidity,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::Overflow => write!(f, "arithmetic overflow"),
+            MathError::InvalidInput => write!(f, "invalid input parameters"),
+            MathError::InsufficientLiquidity => write!(f, "requested output exceeds curve liquidity"),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
+
+pub type MathResult<T> = Result<T, MathError>;
+
+/// Reserves of a bonding curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveReserves {
+    pub virtual_sol: u128,
+    pub virtual_tokens: u128,
+    pub real_sol: u64,
+}
+
+impl CurveReserves {
+    pub fn k(&self) -> MathResult<u128> {
+        self.virtual_sol.checked_mul(self.virtual_tokens).ok_or(MathError::Overflow)
+    }
+}
+
+/// Fee rates charged on every trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub platform_fee_bps: u16,
+    pub yield_fee_bps: u16,
+    pub creator_fee_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn total_bps(&self) -> u128 {
+        self.platform_fee_bps as u128 + self.yield_fee_bps as u128 + self.creator_fee_bps as u128
+    }
+
+    /// Platform and yield fee rates reduced by `discount_bps` of themselves
+    /// (rounded down). The creator's share is not discounted.
+    pub fn with_discount(&self, discount_bps: u16) -> MathResult<FeeSchedule> {
+        let keep = BPS_DENOMINATOR
+            .checked_sub(discount_bps as u128)
+            .ok_or(MathError::InvalidInput)?;
+        let scale = |bps: u16| (bps as u128 * keep / BPS_DENOMINATOR) as u16;
+        Ok(FeeSchedule {
+            platform_fee_bps: scale(self.platform_fee_bps),
+            yield_fee_bps: scale(self.yield_fee_bps),
+            creator_fee_bps: self.creator_fee_bps,
+        })
+    }
+}
+
+/// Fees taken from a SOL amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub platform_fee: u64,
+    pub yield_fee: u64,
+    pub creator_fee: u64,
+}
+
+impl FeeSplit {
+    pub fn total(&self) -> u64 {
+        self.platform_fee + self.yield_fee + self.creator_fee
+    }
+}
+
+/// Result of pricing a trade against a bonding curve. `amount_in` is lamports
+/// on a buy and tokens on a sell; `amount_out` is the opposite asset.
+/// `penalty` is the part of the output withheld by an active sabotage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub platform_fee: u64,
+    pub yield_fee: u64,
+    pub creator_fee: u64,
+    pub penalty: u64,
+    /// Lamports of a buy left unused because the curve hit graduation.
+    pub refund: u64,
+    pub reserves: CurveReserves,
+}
+
+impl SwapQuote {
+    pub fn total_fee(&self) -> u64 {
+        self.platform_fee + self.yield_fee + self.creator_fee
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// HELPERS
+// ═══════════════════════════════════════════════════════════════════════
+
+pub fn bps_of(amount: u128, bps: u16) -> MathResult<u128> {
+    Ok(amount.checked_mul(bps as u128).ok_or(MathError::Overflow)? / BPS_DENOMINATOR)
+}
+
+fn ceil_div(a: u128, b: u128) -> MathResult<u128> {
+    if b == 0 {
+        return Err(MathError::Overflow);
+    }
+    Ok(a.div_ceil(b))
+}
+
+fn to_u64(value: u128) -> MathResult<u64> {
+    u64::try_from(value).map_err(|_| MathError::Overflow)
+}
+
+/// `a * b / c`, rounded down.
+pub fn mul_div(a: u64, b: u64, c: u64) -> MathResult<u64> {
+    let product = (a as u128).checked_mul(b as u128).ok_or(MathError::Overflow)?;
+    to_u64(product.checked_div(c as u128).ok_or(MathError::Overflow)?)
+}
+
+/// Splits the platform, yield and creator fees off a SOL amount (each rounded down).
+pub fn split_fees(amount: u64, fees: &FeeSchedule) -> MathResult<FeeSplit> {
+    let amount = amount as u128;
+    Ok(FeeSplit {
+        platform_fee: to_u64(bps_of(amount, fees.platform_fee_bps)?)?,
+        yield_fee: to_u64(bps_of(amount, fees.yield_fee_bps)?)?,
+        creator_fee: to_u64(bps_of(amount, fees.creator_fee_bps)?)?,
+    })
+}
+
+/// Withholds `penalty_bps` of `amount`. Returns `(kept, withheld)`.
+pub fn apply_penalty(amount: u128, penalty_bps: u16) -> MathResult<(u128, u128)> {
+    let multiplier = BPS_DENOMINATOR
+        .checked_sub(penalty_bps as u128)
+        .ok_or(MathError::InvalidInput)?;
+    let kept = amount.checked_mul(multiplier).ok_or(MathError::Overflow)? / BPS_DENOMINATOR;
+    Ok((kept, amount - kept))
+}
+
+/// Whether a curve with these real reserves has reached its
+/// `graduation_threshold`.
+pub fn is_graduated(real_sol: u64, graduation_threshold: u64) -> bool {
+    real_sol >= graduation_threshold
+}
+
+/// Real reserves a curve can still take in before it graduates at
+/// `graduation_threshold`.
+pub fn graduation_capacity(real_sol: u64, graduation_threshold: u64) -> u64 {
+    graduation_threshold.saturating_sub(real_sol)
+}
+
+/// Anti-sniper fee for a buy `elapsed` seconds after launch: starts at
+/// `max_fee_bps` and decays linearly to zero at the end of `window` seconds.
+pub fn launch_fee_bps(max_fee_bps: u16, elapsed: i64, window: i64) -> u16 {
+    if window <= 0 || elapsed >= window {
+        return 0;
+    }
+    let remaining = (window - elapsed.max(0)) as u128;
+    (max_fee_bps as u128 * remaining / window as u128) as u16
+}
+
+/// Spot price of the curve in lamports per whole token.
+pub fn spot_price(reserves: &CurveReserves) -> MathResult<u64> {
+    let price = reserves
+        .virtual_sol
+        .checked_mul(TOKEN_UNIT)
+        .ok_or(MathError::Overflow)?
+        .checked_div(reserves.virtual_tokens)
+        .ok_or(MathError::InvalidInput)?;
+    to_u64(price)
+}
+
+/// Absolute change from `from` to `to`, in basis points of `from`.
+pub fn price_change_bps(from: u64, to: u64) -> MathResult<u64> {
+    if from == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    to_u64((from.abs_diff(to) as u128) * BPS_DENOMINATOR / from as u128)
+}
+
+/// Adds `price` held for `elapsed` seconds to a cumulative price. Wraps on
+/// overflow; only differences between two readings are meaningful.
+pub fn accumulate_price(cumulative: u128, price: u64, elapsed: i64) -> u128 {
+    cumulative.wrapping_add((price as u128).wrapping_mul(elapsed.max(0) as u128))
+}
+
+/// Time-weighted average price between two cumulative price readings.
+pub fn twap(start_cumulative: u128, start_ts: i64, end_cumulative: u128, end_ts: i64) -> MathResult<u64> {
+    if end_ts <= start_ts {
+        return Err(MathError::InvalidInput);
+    }
+    let elapsed = (end_ts - start_ts) as u128;
+    to_u64(end_cumulative.wrapping_sub(start_cumulative) / elapsed)
+}
+
+/// Relative move of the spot price (`virtual_sol / virtual_tokens`) between
+/// two reserve states, in basis points.
+pub fn price_impact_bps(pre: &CurveReserves, post: &CurveReserves) -> MathResult<u64> {
+    let before = pre.virtual_sol.checked_mul(post.virtual_tokens).ok_or(MathError::Overflow)?;
+    let after = post.virtual_sol.checked_mul(pre.virtual_tokens).ok_or(MathError::Overflow)?;
+    let impact = after
+        .abs_diff(before)
+        .checked_mul(BPS_DENOMINATOR)
+        .ok_or(MathError::Overflow)?
+        .checked_div(before)
+        .ok_or(MathError::Overflow)?;
+    to_u64(impact)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// EXACT INPUT
+// ═══════════════════════════════════════════════════════════════════════
+
+/// BUY: SOL In -> Tokens Out. Fees come off the SOL in, the sabotage penalty
+/// off the tokens out. A buy that would cross the graduation threshold is
+/// filled only up to it; the unused SOL and its share of the fees are
+/// reported as `refund`.
+pub fn buy_exact_in(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    lamports_in: u64,
+) -> MathResult<SwapQuote> {
+    buy_exact_in_with_threshold(reserves, fees, penalty_bps, lamports_in, GRADUATION_THRESHOLD)
+}
+
+/// `buy_exact_in` for a curve that graduates at `graduation_threshold` real
+/// reserves instead of `GRADUATION_THRESHOLD`, e.g. one quoted in an SPL token.
+pub fn buy_exact_in_with_threshold(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    amount_in: u64,
+    graduation_threshold: u64,
+) -> MathResult<SwapQuote> {
+    let capacity = graduation_capacity(reserves.real_sol, graduation_threshold);
+    buy_with_capacity(reserves, fees, penalty_bps, amount_in, capacity)
+}
+
+fn buy_with_capacity(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    lamports_in: u64,
+    capacity: u64,
+) -> MathResult<SwapQuote> {
+    let mut fee = split_fees(lamports_in, fees)?;
+    let mut amount_after_fee = lamports_in.checked_sub(fee.total()).ok_or(MathError::Overflow)?;
+
+    if amount_after_fee > capacity {
+        fee = FeeSplit {
+            platform_fee: mul_div(fee.platform_fee, capacity, amount_after_fee)?,
+            yield_fee: mul_div(fee.yield_fee, capacity, amount_after_fee)?,
+            creator_fee: mul_div(fee.creator_fee, capacity, amount_after_fee)?,
+        };
+        amount_after_fee = capacity;
+    }
+    let amount_in = amount_after_fee + fee.total();
+
+    let x = reserves.virtual_sol;
+    let y = reserves.virtual_tokens;
+    let k = reserves.k()?;
+    let new_x = x.checked_add(amount_after_fee as u128).ok_or(MathError::Overflow)?;
+    // Round the new reserve up so k never decreases.
+    let new_y = ceil_div(k, new_x)?;
+    let tokens_out = y.checked_sub(new_y).ok_or(MathError::Overflow)?;
+
+    let (tokens_out_adjusted, withheld) = apply_penalty(tokens_out, penalty_bps)?;
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out: to_u64(tokens_out_adjusted)?,
+        platform_fee: fee.platform_fee,
+        yield_fee: fee.yield_fee,
+        creator_fee: fee.creator_fee,
+        penalty: to_u64(withheld)?,
+        refund: lamports_in - amount_in,
+        reserves: CurveReserves {
+            virtual_sol: new_x,
+            virtual_tokens: new_y,
+            real_sol: reserves.real_sol.checked_add(amount_after_fee).ok_or(MathError::Overflow)?,
+        },
+    })
+}
+
+/// SELL: Tokens In -> SOL Out. The sabotage penalty comes off the gross SOL
+/// out first, then fees are taken from what remains.
+pub fn sell_exact_in(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    tokens_in: u64,
+) -> MathResult<SwapQuote> {
+    let x = reserves.virtual_sol;
+    let y = reserves.virtual_tokens;
+    let k = reserves.k()?;
+    let new_y = y.checked_add(tokens_in as u128).ok_or(MathError::Overflow)?;
+    // Round the new reserve up so k never decreases.
+    let new_x = ceil_div(k, new_y)?;
+    let sol_out_gross = x.checked_sub(new_x).ok_or(MathError::Overflow)?;
+
+    let (sol_out_adjusted, withheld) = apply_penalty(sol_out_gross, penalty_bps)?;
+    let sol_out_adjusted = to_u64(sol_out_adjusted)?;
+
+    let fee = split_fees(sol_out_adjusted, fees)?;
+    let sol_out_net = sol_out_adjusted.checked_sub(fee.total()).ok_or(MathError::Overflow)?;
+
+    Ok(SwapQuote {
+        amount_in: tokens_in,
+        amount_out: sol_out_net,
+        platform_fee: fee.platform_fee,
+        yield_fee: fee.yield_fee,
+        creator_fee: fee.creator_fee,
+        penalty: to_u64(withheld)?,
+        refund: 0,
+        reserves: CurveReserves {
+            virtual_sol: new_x,
+            virtual_tokens: new_y,
+            // Reduce Real SOL by GROSS amount (what leaves the bonding curve)
+            real_sol: reserves.real_sol.checked_sub(sol_out_adjusted).ok_or(MathError::Overflow)?,
+        },
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// EXACT OUTPUT
+// ═══════════════════════════════════════════════════════════════════════
+
+fn net_bps(fees: &FeeSchedule) -> MathResult<u128> {
+    let net = BPS_DENOMINATOR.checked_sub(fees.total_bps()).ok_or(MathError::InvalidInput)?;
+    if net == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    Ok(net)
+}
+
+fn penalty_multiplier(penalty_bps: u16) -> MathResult<u128> {
+    let multiplier = BPS_DENOMINATOR
+        .checked_sub(penalty_bps as u128)
+        .ok_or(MathError::InvalidInput)?;
+    if multiplier == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    Ok(multiplier)
+}
+
+/// Buys exactly `tokens_out` tokens. Inverts the penalty, constant-product and
+/// fee steps of `buy_exact_in` (rounding against the trader) and then re-runs
+/// the forward path to price that input. Any rounding surplus beyond
+/// `tokens_out` stays in the curve and is kept in `virtual_tokens`.
+pub fn buy_exact_out(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    tokens_out: u64,
+) -> MathResult<SwapQuote> {
+    buy_exact_out_with_threshold(reserves, fees, penalty_bps, tokens_out, GRADUATION_THRESHOLD)
+}
+
+/// `buy_exact_out` for a curve that graduates at `graduation_threshold`.
+pub fn buy_exact_out_with_threshold(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    tokens_out: u64,
+    graduation_threshold: u64,
+) -> MathResult<SwapQuote> {
+    let x = reserves.virtual_sol;
+    let y = reserves.virtual_tokens;
+    let k = reserves.k()?;
+
+    // Tokens the curve must release before the penalty is withheld.
+    let tokens_gross = ceil_div(tokens_out as u128 * BPS_DENOMINATOR, penalty_multiplier(penalty_bps)?)?;
+    if tokens_gross >= y {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    let new_x = ceil_div(k, y - tokens_gross)?;
+    let sol_after_fee = new_x - x;
+    let lamports_in = ceil_div(sol_after_fee * BPS_DENOMINATOR, net_bps(fees)?)?;
+
+    let mut quote = buy_exact_in_with_threshold(reserves, fees, penalty_bps, to_u64(lamports_in)?, graduation_threshold)?;
+    if quote.refund > 0 {
+        // The graduation cap would leave the trader short of `tokens_out`.
+        return Err(MathError::InsufficientLiquidity);
+    }
+    if quote.amount_out < tokens_out {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    // Only the requested tokens (and the withheld penalty) leave the reserves.
+    let surplus = quote.amount_out - tokens_out;
+    quote.reserves.virtual_tokens = quote.reserves.virtual_tokens
+        .checked_add(surplus as u128)
+        .ok_or(MathError::Overflow)?;
+    quote.amount_out = tokens_out;
+    Ok(quote)
+}
+
+/// Sells just enough tokens to receive exactly `lamports_out` after penalty and
+/// fees. Any rounding surplus stays in the curve and is kept in `real_sol`.
+pub fn sell_exact_out(
+    reserves: &CurveReserves,
+    fees: &FeeSchedule,
+    penalty_bps: u16,
+    lamports_out: u64,
+) -> MathResult<SwapQuote> {
+    let x = reserves.virtual_sol;
+    let y = reserves.virtual_tokens;
+    let k = reserves.k()?;
+
+    // SOL the curve must release so that, after penalty and fees, `lamports_out` remains.
+    let sol_adjusted = ceil_div(lamports_out as u128 * BPS_DENOMINATOR, net_bps(fees)?)?;
+    let sol_gross = ceil_div(sol_adjusted * BPS_DENOMINATOR, penalty_multiplier(penalty_bps)?)?;
+    if sol_gross >= x {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    let new_y = ceil_div(k, x - sol_gross)?;
+    let tokens_in = new_y - y;
+
+    let mut quote = sell_exact_in(reserves, fees, penalty_bps, to_u64(tokens_in)?)?;
+    if quote.amount_out < lamports_out {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    // Only fees and the requested lamports leave the curve.
+    let surplus = quote.amount_out - lamports_out;
+    quote.reserves.real_sol = quote.reserves.real_sol.checked_add(surplus).ok_or(MathError::Overflow)?;
+    quote.amount_out = lamports_out;
+    Ok(quote)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// AMM POOL
+// ═══════════════════════════════════════════════════════════════════════
+
+// Pool quotes reuse `SwapQuote`: `reserves.real_sol` is the new SOL reserve and
+// `reserves.virtual_tokens` the new token reserve (there are no virtual
+// reserves once a curve has migrated).
+
+fn pool_reserves(sol_reserve: u64, token_reserve: u64) -> CurveReserves {
+    CurveReserves {
+        virtual_sol: sol_reserve as u128,
+        virtual_tokens: token_reserve as u128,
+        real_sol: sol_reserve,
+    }
+}
+
+/// Buys from a graduated pool. Same fee ordering as the curve, no sabotage
+/// penalty and no graduation cap.
+pub fn pool_buy_exact_in(
+    sol_reserve: u64,
+    token_reserve: u64,
+    fees: &FeeSchedule,
+    lamports_in: u64,
+) -> MathResult<SwapQuote> {
+    buy_with_capacity(&pool_reserves(sol_reserve, token_reserve), fees, 0, lamports_in, u64::MAX)
+}
+
+/// Sells into a graduated pool. Fees are taken from the SOL out.
+pub fn pool_sell_exact_in(
+    sol_reserve: u64,
+    token_reserve: u64,
+    fees: &FeeSchedule,
+    tokens_in: u64,
+) -> MathResult<SwapQuote> {
+    sell_exact_in(&pool_reserves(sol_reserve, token_reserve), fees, 0, tokens_in)
+}
+
+/// Amounts moved by a liquidity deposit or withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidityQuote {
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+}
+
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// LP supply minted when a pool is seeded: `sqrt(sol * tokens)`.
+pub fn initial_lp_supply(sol_reserve: u64, token_reserve: u64) -> MathResult<u64> {
+    let product = (sol_reserve as u128)
+        .checked_mul(token_reserve as u128)
+        .ok_or(MathError::Overflow)?;
+    let lp = to_u64(isqrt(product))?;
+    if lp == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    Ok(lp)
+}
+
+/// Largest proportional deposit within `max_sol` / `max_tokens`. The LP minted
+/// is rounded down and the assets taken are rounded up, both in the pool's
+/// favour.
+pub fn add_liquidity_quote(
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+    max_sol: u64,
+    max_tokens: u64,
+) -> MathResult<LiquidityQuote> {
+    if sol_reserve == 0 || token_reserve == 0 || lp_supply == 0 {
+        return Err(MathError::InsufficientLiquidity);
+    }
+    let lp_amount = mul_div(max_sol, lp_supply, sol_reserve)?
+        .min(mul_div(max_tokens, lp_supply, token_reserve)?);
+    if lp_amount == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    let sol_amount = to_u64(ceil_div(lp_amount as u128 * sol_reserve as u128, lp_supply as u128)?)?;
+    let token_amount = to_u64(ceil_div(lp_amount as u128 * token_reserve as u128, lp_supply as u128)?)?;
+    Ok(LiquidityQuote { lp_amount, sol_amount, token_amount })
+}
+
+/// Pro-rata share of both reserves for burning `lp_amount`, rounded down.
+pub fn remove_liquidity_quote(
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+    lp_amount: u64,
+) -> MathResult<LiquidityQuote> {
+    if lp_amount == 0 || lp_amount > lp_supply {
+        return Err(MathError::InvalidInput);
+    }
+    Ok(LiquidityQuote {
+        lp_amount,
+        sol_amount: mul_div(lp_amount, sol_reserve, lp_supply)?,
+        token_amount: mul_div(lp_amount, token_reserve, lp_supply)?,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// YIELD
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Reward-per-weight index after spreading `reward` over `total_weight`, the
+/// vault's stakes weighted by their lock multipliers. Rounds down, so the dust
+/// stays in the vault.
+pub fn reward_index_after(index: u128, reward: u64, total_weight: u64) -> MathResult<u128> {
+    if total_weight == 0 {
+        return Err(MathError::InvalidInput);
+    }
+    let increment = (reward as u128)
+        .checked_mul(REWARD_INDEX_SCALE)
+        .ok_or(MathError::Overflow)?
+        / total_weight as u128;
+    index.checked_add(increment).ok_or(MathError::Overflow)
+}
+
+/// Part of a `pending` reward pot released `elapsed` seconds into a stream
+/// over `period`: proportional to the time, and all of it once a full period
+/// has passed. Rounds down.
+pub fn streamed_reward(pending: u64, elapsed: i64, period: i64) -> MathResult<u64> {
+    if period <= 0 {
+        return Err(MathError::InvalidInput);
+    }
+    if elapsed <= 0 {
+        return Ok(0);
+    }
+    if elapsed >= period {
+        return Ok(pending);
+    }
+    mul_div(pending, elapsed as u64, period as u64)
+}
+
+/// Rewards earned by `staked` tokens while the index moved from `index_paid`
+/// to `index`, rounded down.
+pub fn rewards_earned(staked: u64, index: u128, index_paid: u128) -> MathResult<u64> {
+    let delta = index.checked_sub(index_paid).ok_or(MathError::InvalidInput)?;
+    to_u64(delta.checked_mul(staked as u128).ok_or(MathError::Overflow)? / REWARD_INDEX_SCALE)
+}
@@ -0,0 +1,316 @@
+use marscorp_math::*;
+use proptest::prelude::*;
+
+fn fresh_curve() -> CurveReserves {
+    CurveReserves {
+        virtual_sol: INITIAL_VIRTUAL_SOL,
+        virtual_tokens: INITIAL_VIRTUAL_TOKENS,
+        real_sol: 0,
+    }
+}
+
+fn reserves() -> impl Strategy<Value = CurveReserves> {
+    // Any state reachable by buying up to the graduation threshold.
+    (0u64..=GRADUATION_THRESHOLD).prop_map(|lamports| {
+        let fees = FeeSchedule { platform_fee_bps: 0, yield_fee_bps: 0, creator_fee_bps: 0 };
+        buy_exact_in(&fresh_curve(), &fees, 0, lamports).unwrap().reserves
+    })
+}
+
+fn fees() -> impl Strategy<Value = FeeSchedule> {
+    (0u16..=500, 0u16..=500, 0u16..=200).prop_map(|(platform_fee_bps, yield_fee_bps, creator_fee_bps)| {
+        FeeSchedule { platform_fee_bps, yield_fee_bps, creator_fee_bps }
+    })
+}
+
+fn penalty() -> impl Strategy<Value = u16> {
+    prop_oneof![Just(0u16), Just(100u16), 0u16..=5_000]
+}
+
+proptest! {
+    #[test]
+    fn buy_never_decreases_k(r in reserves(), f in fees(), p in penalty(), sol in 1u64..=50_000_000_000) {
+        let q = buy_exact_in(&r, &f, p, sol).unwrap();
+        prop_assert!(q.reserves.k().unwrap() >= r.k().unwrap());
+    }
+
+    #[test]
+    fn buys_raise_and_sells_lower_spot_price(r in reserves(), f in fees(), p in penalty(), sol in 1u64..=50_000_000_000) {
+        let buy = buy_exact_in(&r, &f, p, sol).unwrap();
+        prop_assert!(spot_price(&buy.reserves).unwrap() >= spot_price(&r).unwrap());
+        let sell = sell_exact_in(&buy.reserves, &f, p, buy.amount_out).unwrap();
+        prop_assert!(spot_price(&sell.reserves).unwrap() <= spot_price(&buy.reserves).unwrap());
+    }
+
+    #[test]
+    fn sell_never_decreases_k(r in reserves(), f in fees(), p in penalty(), tokens in 1u64..=CURVE_SUPPLY) {
+        if let Ok(q) = sell_exact_in(&r, &f, p, tokens) {
+            prop_assert!(q.reserves.k().unwrap() >= r.k().unwrap());
+        }
+    }
+
+    #[test]
+    fn buy_never_beats_spot_price(r in reserves(), f in fees(), p in penalty(), sol in 0u64..=50_000_000_000) {
+        let q = buy_exact_in(&r, &f, p, sol).unwrap();
+        let net_in = (q.amount_in - q.total_fee()) as u128;
+        // Tokens out can never exceed what the pre-trade spot price would give.
+        prop_assert!((q.amount_out as u128) * r.virtual_sol <= net_in * r.virtual_tokens);
+        if net_in == 0 {
+            prop_assert_eq!(q.amount_out, 0);
+        }
+    }
+
+    #[test]
+    fn buy_fees_and_reserves_add_up(r in reserves(), f in fees(), p in penalty(), sol in 0u64..=50_000_000_000) {
+        let q = buy_exact_in(&r, &f, p, sol).unwrap();
+        let net_in = q.amount_in - q.total_fee();
+        prop_assert_eq!(q.reserves.real_sol, r.real_sol + net_in);
+        prop_assert_eq!(q.reserves.virtual_sol, r.virtual_sol + net_in as u128);
+        prop_assert_eq!(
+            r.virtual_tokens - q.reserves.virtual_tokens,
+            (q.amount_out + q.penalty) as u128
+        );
+    }
+
+    #[test]
+    fn buy_never_overshoots_graduation(r in reserves(), f in fees(), p in penalty(), sol in 1u64..=200_000_000_000) {
+        let q = buy_exact_in(&r, &f, p, sol).unwrap();
+        prop_assert!(q.reserves.real_sol <= GRADUATION_THRESHOLD);
+        prop_assert_eq!(q.amount_in + q.refund, sol);
+        if q.refund > 0 {
+            prop_assert_eq!(q.reserves.real_sol, GRADUATION_THRESHOLD);
+            // Refunded SOL carries its share of the fees back with it.
+            let full = split_fees(sol, &f).unwrap();
+            prop_assert!(q.platform_fee <= full.platform_fee);
+            prop_assert!(q.yield_fee <= full.yield_fee);
+            prop_assert!(q.creator_fee <= full.creator_fee);
+        }
+    }
+
+    #[test]
+    fn buy_respects_custom_graduation_threshold(
+        f in fees(),
+        threshold in 1u64..=1_000_000_000_000,
+        amount in 1u64..=2_000_000_000_000,
+    ) {
+        let q = buy_exact_in_with_threshold(&fresh_curve(), &f, 0, amount, threshold).unwrap();
+        prop_assert!(q.reserves.real_sol <= threshold);
+        prop_assert_eq!(q.amount_in + q.refund, amount);
+        if q.refund > 0 {
+            prop_assert_eq!(q.reserves.real_sol, threshold);
+        }
+    }
+
+    #[test]
+    fn round_trip_never_profits(r in reserves(), f in fees(), p in penalty(), sol in 1u64..=50_000_000_000) {
+        let buy = buy_exact_in(&r, &f, p, sol).unwrap();
+        let sell = sell_exact_in(&buy.reserves, &f, p, buy.amount_out).unwrap();
+        prop_assert!(sell.amount_out <= sol);
+        prop_assert!(sell.reserves.k().unwrap() >= r.k().unwrap());
+    }
+
+    #[test]
+    fn buy_exact_out_delivers_requested(r in reserves(), f in fees(), p in penalty(), tokens in 1u64..=100_000_000_000_000) {
+        match buy_exact_out(&r, &f, p, tokens) {
+            Ok(q) => {
+                prop_assert_eq!(q.amount_out, tokens);
+                let forward = buy_exact_in(&r, &f, p, q.amount_in).unwrap();
+                prop_assert!(forward.amount_out >= tokens);
+                prop_assert_eq!(forward.reserves.virtual_sol, q.reserves.virtual_sol);
+                prop_assert_eq!(forward.reserves.real_sol, q.reserves.real_sol);
+                // The rounding surplus stays in the token reserve.
+                prop_assert_eq!(
+                    r.virtual_tokens - q.reserves.virtual_tokens,
+                    (tokens + q.penalty) as u128
+                );
+            }
+            // Only allowed when the graduation cap stands in the way.
+            Err(e) => prop_assert_eq!(e, MathError::InsufficientLiquidity),
+        }
+    }
+
+    #[test]
+    fn sell_exact_out_delivers_requested(r in reserves(), f in fees(), p in penalty(), lamports in 1u64..=10_000_000_000) {
+        if let Ok(q) = sell_exact_out(&r, &f, p, lamports) {
+            prop_assert_eq!(q.amount_out, lamports);
+            let forward = sell_exact_in(&r, &f, p, q.amount_in).unwrap();
+            prop_assert!(forward.amount_out >= lamports);
+            // The rounding surplus is kept as real SOL.
+            prop_assert_eq!(
+                r.real_sol - q.reserves.real_sol,
+                q.total_fee() + lamports
+            );
+        }
+    }
+
+    #[test]
+    fn pool_round_trip_never_profits(sol in 1u64..=100_000_000_000, tokens in 1_000_000u64..=CURVE_SUPPLY, f in fees(), lamports in 1u64..=50_000_000_000) {
+        let buy = pool_buy_exact_in(sol, tokens, &f, lamports).unwrap();
+        let sell = pool_sell_exact_in(
+            buy.reserves.real_sol,
+            buy.reserves.virtual_tokens as u64,
+            &f,
+            buy.amount_out,
+        ).unwrap();
+        prop_assert_eq!(buy.refund, 0);
+        prop_assert!(sell.amount_out <= lamports);
+        prop_assert!(sell.reserves.k().unwrap() >= (sol as u128) * (tokens as u128));
+    }
+
+    #[test]
+    fn liquidity_round_trip_never_profits(
+        sol in 1_000u64..=100_000_000_000,
+        tokens in 1_000u64..=CURVE_SUPPLY,
+        max_sol in 1u64..=100_000_000_000,
+        max_tokens in 1u64..=CURVE_SUPPLY,
+    ) {
+        let supply = initial_lp_supply(sol, tokens).unwrap();
+        if let Ok(add) = add_liquidity_quote(sol, tokens, supply, max_sol, max_tokens) {
+            prop_assert!(add.sol_amount <= max_sol && add.token_amount <= max_tokens);
+            let remove = remove_liquidity_quote(
+                sol + add.sol_amount,
+                tokens + add.token_amount,
+                supply + add.lp_amount,
+                add.lp_amount,
+            ).unwrap();
+            prop_assert!(remove.sol_amount <= add.sol_amount);
+            prop_assert!(remove.token_amount <= add.token_amount);
+        }
+    }
+
+    #[test]
+    fn discounts_never_raise_fees(f in fees(), discount in 0u16..=10_000, amount in any::<u64>()) {
+        let discounted = f.with_discount(discount).unwrap();
+        let full = split_fees(amount, &f).unwrap();
+        let cheaper = split_fees(amount, &discounted).unwrap();
+        prop_assert!(cheaper.platform_fee <= full.platform_fee);
+        prop_assert!(cheaper.yield_fee <= full.yield_fee);
+        prop_assert_eq!(cheaper.creator_fee, full.creator_fee);
+        prop_assert_eq!(f.with_discount(0).unwrap(), f);
+    }
+
+    #[test]
+    fn launch_fee_decays_monotonically(max in 0u16..=5_000, window in 1i64..=86_400, t in 0i64..=100_000) {
+        let now = launch_fee_bps(max, t, window);
+        let later = launch_fee_bps(max, t + 1, window);
+        prop_assert!(now <= max);
+        prop_assert!(later <= now);
+        if t >= window {
+            prop_assert_eq!(now, 0);
+        }
+    }
+
+    #[test]
+    fn split_fees_never_exceed_amount(amount in any::<u64>(), f in fees()) {
+        let split = split_fees(amount, &f).unwrap();
+        prop_assert!(split.total() as u128 <= amount as u128 * f.total_bps() / BPS_DENOMINATOR);
+    }
+
+    #[test]
+    fn twap_of_constant_price_is_that_price(start in any::<u128>(), price in any::<u64>(), ts in 0i64..=1_000_000_000, elapsed in 1i64..=1_000_000) {
+        let end = accumulate_price(start, price, elapsed);
+        prop_assert_eq!(twap(start, ts, end, ts + elapsed).unwrap(), price);
+    }
+
+    #[test]
+    fn twap_lies_between_segment_prices(a in 0u64..=1_000_000_000, b in 0u64..=1_000_000_000, ta in 1i64..=100_000, tb in 1i64..=100_000) {
+        let mid = accumulate_price(0, a, ta);
+        let end = accumulate_price(mid, b, tb);
+        let avg = twap(0, 0, end, ta + tb).unwrap();
+        prop_assert!(avg >= a.min(b) && avg <= a.max(b));
+    }
+
+    #[test]
+    fn stakers_never_claim_more_than_distributed(
+        stakes in proptest::collection::vec(1u64..=1_000_000_000_000_000, 1..8),
+        rewards in proptest::collection::vec(0u64..=100_000_000_000, 1..8),
+    ) {
+        let total: u64 = stakes.iter().sum();
+        let mut index = 0u128;
+        for reward in &rewards {
+            index = reward_index_after(index, *reward, total).unwrap();
+        }
+        let claimed: u64 = stakes.iter().map(|s| rewards_earned(*s, index, 0).unwrap()).sum();
+        let distributed: u64 = rewards.iter().sum();
+        prop_assert!(claimed <= distributed);
+        // Rounding dust: the index truncation of each distribution, plus one unit per staker.
+        let per_distribution = (total as u128 / REWARD_INDEX_SCALE) as u64 + 1;
+        prop_assert!(distributed - claimed <= per_distribution * rewards.len() as u64 + stakes.len() as u64);
+    }
+
+    #[test]
+    fn late_staker_only_earns_the_streamed_part_of_an_earlier_pot(
+        pot in 0u64..=1_000_000_000_000,
+        fee in 0u64..=100_000_000_000,
+        weight in 1u64..=1_000_000_000_000_000,
+        staked_for in 0i64..YIELD_STREAM_SECS,
+    ) {
+        // `pot` accrued with nothing staked; a staker joins, and `staked_for`
+        // seconds later the next fee arrives
+        let released = streamed_reward(pot, staked_for, YIELD_STREAM_SECS).unwrap();
+        let index = reward_index_after(0, released, weight).unwrap();
+        let index = reward_index_after(index, fee, weight).unwrap();
+        let earned = rewards_earned(weight, index, 0).unwrap();
+        prop_assert!(released as u128 <= pot as u128 * staked_for as u128 / YIELD_STREAM_SECS as u128);
+        prop_assert!(earned <= fee + released);
+    }
+
+    #[test]
+    fn stream_releases_the_whole_pot_after_a_period(pot in any::<u64>(), extra in 0i64..=1_000_000) {
+        prop_assert_eq!(streamed_reward(pot, YIELD_STREAM_SECS + extra, YIELD_STREAM_SECS).unwrap(), pot);
+    }
+
+    #[test]
+    fn penalty_conserves_amount(amount in any::<u64>(), p in 0u16..=10_000) {
+        let (kept, withheld) = apply_penalty(amount as u128, p).unwrap();
+        prop_assert_eq!(kept + withheld, amount as u128);
+        prop_assert!(withheld <= amount as u128 * p as u128 / BPS_DENOMINATOR + 1);
+    }
+}
+
+#[test]
+fn stake_placed_just_before_a_fee_gets_none_of_the_pot() {
+    let pot = 50_000_000_000;
+    let fee = 1_000;
+    let released = streamed_reward(pot, 0, YIELD_STREAM_SECS).unwrap();
+    assert_eq!(released, 0);
+    let index = reward_index_after(0, released + fee, 1).unwrap();
+    assert_eq!(rewards_earned(1, index, 0).unwrap(), fee);
+}
+
+#[test]
+fn graduates_at_threshold() {
+    assert!(!is_graduated(GRADUATION_THRESHOLD - 1, GRADUATION_THRESHOLD));
+    assert!(is_graduated(GRADUATION_THRESHOLD, GRADUATION_THRESHOLD));
+    assert_eq!(graduation_capacity(GRADUATION_THRESHOLD - 1, GRADUATION_THRESHOLD), 1);
+    assert_eq!(graduation_capacity(GRADUATION_THRESHOLD + 1, GRADUATION_THRESHOLD), 0);
+}
+
+#[test]
+fn price_impact_of_identical_reserves_is_zero() {
+    let r = fresh_curve();
+    assert_eq!(price_impact_bps(&r, &r).unwrap(), 0);
+}
+
+#[test]
+fn price_change_is_symmetric_in_direction() {
+    assert_eq!(price_change_bps(100, 150).unwrap(), 5_000);
+    assert_eq!(price_change_bps(100, 50).unwrap(), 5_000);
+    assert_eq!(price_change_bps(100, 1_000).unwrap(), 90_000);
+    assert_eq!(price_change_bps(0, 1), Err(MathError::InvalidInput));
+}
+
+#[test]
+fn initial_lp_is_geometric_mean() {
+    assert_eq!(initial_lp_supply(4, 9).unwrap(), 6);
+    assert_eq!(initial_lp_supply(85_000_000_000, 85_000_000_000).unwrap(), 85_000_000_000);
+    assert_eq!(initial_lp_supply(0, 9), Err(MathError::InvalidInput));
+}
+
+#[test]
+fn launch_fee_starts_at_max() {
+    assert_eq!(launch_fee_bps(1_000, 0, 60), 1_000);
+    assert_eq!(launch_fee_bps(1_000, 30, 60), 500);
+    assert_eq!(launch_fee_bps(1_000, 60, 60), 0);
+    assert_eq!(launch_fee_bps(1_000, 0, 0), 0);
+}
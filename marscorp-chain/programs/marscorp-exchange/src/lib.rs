@@ -1,8 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::{self, spl_token_2022, Token2022};
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, InitializeAccount3, InitializeMint2, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    MetadataPointerInitialize, TokenMetadataInitialize, TokenMetadataUpdateField, TransferFeeInitialize,
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::metadata::{self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata, UpdateMetadataAccountsV2};
 use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use marscorp_math::{self as math, CurveReserves, FeeSchedule, MathError, SwapQuote};
 
 declare_id!("5GKfHwujgiKLXP84f28HyGL5FJ3AnunKsVGmKDmG6RXi");
 
@@ -19,11 +32,418 @@ pub mod marscorp_unified {
         platform_fee_bps: u16, 
         yield_fee_bps: u16
     ) -> Result<()> {
+        check_fee_bounds(platform_fee_bps, yield_fee_bps, 0)?;
+
         let config = &mut ctx.accounts.config;
         config.admin = ctx.accounts.admin.key();
+        config.pending_admin = Pubkey::default();
         config.platform_fee_bps = platform_fee_bps;
         config.yield_fee_bps = yield_fee_bps;
         config.yield_distributor = ctx.accounts.yield_distributor.key();
+        config.mars_mint = Pubkey::default();
+        config.fee_tier_count = 0;
+        config.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        config.creator_fee_bps = 0;
+        config.referral_share_bps = 0;
+        config.launch_window_secs = 0;
+        config.launch_max_buy_per_tx = 0;
+        config.launch_max_buy_per_wallet = 0;
+        config.launch_fee_bps = 0;
+        config.circuit_breaker_bps = 0;
+        config.circuit_breaker_window_secs = 0;
+        config.circuit_breaker_cooloff_secs = 0;
+        config.paused = 0;
+        config.withdrawals_paused = false;
+        config.admin_delay_secs = 0;
+        config.action_count = 0;
+        config.quote_asset_count = 0;
+        config.quote_assets = [QuoteAsset::default(); MAX_QUOTE_ASSETS];
+        Ok(())
+    }
+
+    /// Updates the trading fees and the yield distributor. Platform, yield and
+    /// creator fees together are capped at `MAX_TRADING_FEE_BPS`.
+    pub fn update_config(
+        ctx: Context<AdminConfig>,
+        platform_fee_bps: u16,
+        yield_fee_bps: u16,
+        yield_distributor: Pubkey
+    ) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(
+            &mut ctx.accounts.config,
+            &AdminActionKind::UpdateConfig { platform_fee_bps, yield_fee_bps, yield_distributor },
+        )
+    }
+
+    /// First step of an admin rotation; takes effect once `new_admin` accepts.
+    /// Proposing `Pubkey::default()` withdraws a pending proposal.
+    pub fn propose_admin(ctx: Context<AdminConfig>, new_admin: Pubkey) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::ProposeAdmin { new_admin })
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_admin = config.admin;
+        config.admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
+
+        emit!(AdminChanged {
+            old_admin,
+            new_admin: config.admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pauses instruction families (`PAUSE_*` bits) immediately. Withdrawals
+    /// have their own switch so pausing trading never traps user funds.
+    pub fn set_pause(ctx: Context<AdminConfig>, paused: u8, withdrawals_paused: bool) -> Result<()> {
+        require!(paused & !PAUSE_ALL == 0, UnifiedError::InvalidInput);
+
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+        config.withdrawals_paused = withdrawals_paused;
+
+        emit!(PauseUpdated {
+            paused,
+            withdrawals_paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Configures the per-curve circuit breaker: a curve whose spot price moves
+    /// more than `threshold_bps` within `window_secs` halts for `cooloff_secs`.
+    /// A zero threshold disables it.
+    pub fn set_circuit_breaker(
+        ctx: Context<AdminConfig>,
+        threshold_bps: u16,
+        window_secs: i64,
+        cooloff_secs: i64
+    ) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(
+            &mut ctx.accounts.config,
+            &AdminActionKind::SetCircuitBreaker { threshold_bps, window_secs, cooloff_secs },
+        )
+    }
+
+    /// Configures the anti-sniper window that follows every launch. During the
+    /// first `window_secs` of a curve, buys are capped per transaction and per
+    /// wallet (in lamports) and pay an extra fee to the yield distributor that
+    /// decays linearly from `launch_fee_bps` to zero. A zero limit disables it.
+    pub fn set_launch_protection(
+        ctx: Context<AdminConfig>,
+        window_secs: i64,
+        max_buy_per_tx: u64,
+        max_buy_per_wallet: u64,
+        launch_fee_bps: u16
+    ) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(
+            &mut ctx.accounts.config,
+            &AdminActionKind::SetLaunchProtection { window_secs, max_buy_per_tx, max_buy_per_wallet, launch_fee_bps },
+        )
+    }
+
+    /// Sets the share of the platform fee paid to referrers.
+    pub fn set_referral_share(ctx: Context<AdminConfig>, referral_share_bps: u16) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::SetReferralShare { referral_share_bps })
+    }
+
+    /// Sets the share of every trade that accrues to the business creator,
+    /// capped at `MAX_CREATOR_FEE_BPS`.
+    pub fn set_creator_fee(ctx: Context<AdminConfig>, creator_fee_bps: u16) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::SetCreatorFee { creator_fee_bps })
+    }
+
+    /// Sets the $MARS fee-discount table. Tiers must be sorted by ascending
+    /// `min_balance`; the highest tier a trader qualifies for applies.
+    pub fn set_fee_tiers(ctx: Context<AdminConfig>, mars_mint: Pubkey, tiers: Vec<FeeTier>) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::SetFeeTiers { mars_mint, tiers })
+    }
+
+    /// Allowlists an SPL quote mint for new curves, or updates its parameters.
+    /// Native SOL is always available and uses the program-wide settings.
+    /// SPL-quoted curves do not graduate; they keep trading on the curve.
+    pub fn set_quote_asset(ctx: Context<AdminConfig>, asset: QuoteAsset) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::SetQuoteAsset { asset })
+    }
+
+    /// Removes a quote mint from the allowlist. Existing curves keep trading.
+    pub fn remove_quote_asset(ctx: Context<AdminConfig>, mint: Pubkey) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::RemoveQuoteAsset { mint })
+    }
+
+    /// Sets the minimum delay between queueing and executing an admin action.
+    /// While it is zero the setters above apply directly; once it is set they
+    /// have to go through `queue_action`, including changes to the delay itself.
+    pub fn set_admin_delay(ctx: Context<AdminConfig>, delay_secs: i64) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_admin_action(&mut ctx.accounts.config, &AdminActionKind::SetAdminDelay { delay_secs })
+    }
+
+    /// Grows a config written by an older program version to the current
+    /// layout. Appended fields start zeroed, which is how `initialize_config`
+    /// sets them too. Idempotent.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config = ctx.accounts.config.to_account_info();
+        {
+            let data = config.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == *GlobalConfig::DISCRIMINATOR,
+                UnifiedError::InvalidInput
+            );
+            require_keys_eq!(
+                Pubkey::try_from(&data[8..8 + 32]).map_err(|_| UnifiedError::InvalidInput)?,
+                ctx.accounts.admin.key(),
+                UnifiedError::Unauthorized
+            );
+        }
+        grow_account(&config, CONFIG_SPACE, &ctx.accounts.admin, &ctx.accounts.system_program)
+    }
+
+    /// Brings a curve launched by an older program version up to the current
+    /// layout and creates the creator fee vault it predates. Permissionless
+    /// and idempotent.
+    pub fn migrate_curve(ctx: Context<MigrateCurve>) -> Result<()> {
+        let curve_info = ctx.accounts.curve.to_account_info();
+        let legacy = curve_info.data_len() < CURVE_SPACE;
+        grow_account(&curve_info, CURVE_SPACE, &ctx.accounts.payer, &ctx.accounts.system_program)?;
+
+        if legacy {
+            let mut curve = BondingCurve::try_deserialize(&mut &curve_info.try_borrow_data()?[..])?;
+            let now = Clock::get()?.unix_timestamp;
+            // Zero would graduate the curve on its next trade; the oracle and
+            // breaker start measuring from now rather than from the epoch
+            curve.graduation_threshold = math::GRADUATION_THRESHOLD;
+            curve.last_price_update = now;
+            curve.breaker_window_start = now;
+            curve.breaker_reference_price = math::spot_price(&curve_reserves(&curve)).map_err(UnifiedError::from)?;
+            curve.try_serialize(&mut &mut curve_info.try_borrow_mut_data()?[..])?;
+        }
+
+        let vault = &mut ctx.accounts.creator_fee_vault;
+        if vault.mint == Pubkey::default() {
+            vault.mint = ctx.accounts.mint.key();
+            vault.pending = 0;
+            vault.total_claimed = 0;
+            vault.bump = ctx.bumps.creator_fee_vault;
+        }
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // ADMIN TIMELOCK
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Queues an admin action that becomes executable `admin_delay_secs` from
+    /// now, giving holders warning before it lands.
+    pub fn queue_action(ctx: Context<QueueAction>, action: AdminActionKind) -> Result<()> {
+        if let AdminActionKind::SetFeeTiers { tiers, .. } = &action {
+            require!(tiers.len() <= MAX_FEE_TIERS, UnifiedError::InvalidInput);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let id = config.action_count;
+        config.action_count = id.checked_add(1).ok_or(UnifiedError::Overflow)?;
+
+        let queued = &mut ctx.accounts.action;
+        queued.id = id;
+        queued.action = action;
+        queued.queued_at = now;
+        queued.eta = now.checked_add(config.admin_delay_secs).ok_or(UnifiedError::Overflow)?;
+        queued.bump = ctx.bumps.action;
+
+        emit!(AdminActionQueued {
+            id,
+            action: queued.action.clone(),
+            eta: queued.eta,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_action(ctx: Context<CancelAction>) -> Result<()> {
+        emit!(AdminActionCancelled {
+            id: ctx.accounts.action.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Applies a queued action once its timelock has expired. Seizures need the
+    /// target vesting account and its curve, market overrides the market; other
+    /// actions only touch the config.
+    pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let queued = &ctx.accounts.action;
+        require!(now >= queued.eta, UnifiedError::TimelockNotExpired);
+
+        match &queued.action {
+            AdminActionKind::SeizeLockedTokens { vesting, new_owner } => {
+                check_not_paused(&ctx.accounts.config, PAUSE_VESTING)?;
+                let (Some(vesting_account), Some(curve)) =
+                    (ctx.accounts.vesting.as_mut(), ctx.accounts.curve.as_mut())
+                else {
+                    return err!(UnifiedError::InvalidInput);
+                };
+                require!(vesting_account.key() == *vesting, UnifiedError::InvalidInput);
+                require!(curve.mint == vesting_account.mint, UnifiedError::InvalidInput);
+                seize_vesting(vesting_account, curve, *new_owner);
+            }
+            AdminActionKind::OverrideMarket { market, outcome } => {
+                let market_account = ctx.accounts.market.as_mut().ok_or(UnifiedError::InvalidInput)?;
+                require!(market_account.key() == *market, UnifiedError::InvalidInput);
+                apply_market_override(market_account, *outcome)?;
+            }
+            action => apply_admin_action(&mut ctx.accounts.config, action)?,
+        }
+
+        emit!(AdminActionExecuted {
+            id: queued.id,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // MULTISIG ADMIN
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Creates an M-of-N multisig. Its signer PDA (`[b"multisig_signer",
+    /// multisig]`) can be made `config.admin` through the usual two-step rotation.
+    pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        check_multisig_signers(&signers, threshold)?;
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.creator = ctx.accounts.creator.key();
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.proposal_count = 0;
+        multisig.owner_set_seqno = 0;
+        multisig.signer_bump = ctx.bumps.multisig_signer;
+        multisig.bump = ctx.bumps.multisig;
+
+        emit!(MultisigCreated {
+            multisig: multisig.key(),
+            multisig_signer: ctx.accounts.multisig_signer.key(),
+            signers: multisig.signers.clone(),
+            threshold,
+        });
+        Ok(())
+    }
+
+    /// Proposes an instruction to be signed by the multisig signer PDA, e.g. an
+    /// admin-gated instruction of this program. The proposer's approval counts.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let index = multisig_signer_index(multisig, &ctx.accounts.proposer.key())?;
+        let id = multisig.proposal_count;
+        multisig.proposal_count = id.checked_add(1).ok_or(UnifiedError::Overflow)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.multisig = multisig.key();
+        proposal.id = id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.program_id = program_id;
+        proposal.accounts = accounts;
+        proposal.data = data;
+        proposal.approvals = vec![false; multisig.signers.len()];
+        proposal.approvals[index] = true;
+        proposal.owner_set_seqno = multisig.owner_set_seqno;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(ProposalCreated {
+            multisig: proposal.multisig,
+            id,
+            proposer: proposal.proposer,
+            program_id,
+        });
+        Ok(())
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.owner_set_seqno == multisig.owner_set_seqno, UnifiedError::StaleProposal);
+
+        let index = multisig_signer_index(multisig, &ctx.accounts.signer.key())?;
+        proposal.approvals[index] = true;
+
+        emit!(ProposalApproved {
+            multisig: proposal.multisig,
+            id: proposal.id,
+            signer: ctx.accounts.signer.key(),
+            approvals: proposal.approvals.iter().filter(|a| **a).count() as u8,
+        });
+        Ok(())
+    }
+
+    /// Executes a proposal that reached the threshold, signing as the multisig
+    /// signer PDA. Every account the instruction uses, plus its program, goes in
+    /// the remaining accounts. The proposal is closed to its proposer.
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.owner_set_seqno == multisig.owner_set_seqno, UnifiedError::StaleProposal);
+        let approvals = proposal.approvals.iter().filter(|a| **a).count();
+        require!(approvals >= multisig.threshold as usize, UnifiedError::ThresholdNotMet);
+
+        let signer_key = ctx.accounts.multisig_signer.key();
+        let ix = Instruction {
+            program_id: proposal.program_id,
+            accounts: proposal
+                .accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.pubkey,
+                    is_signer: a.is_signer || a.pubkey == signer_key,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: proposal.data.clone(),
+        };
+        let multisig_key = multisig.key();
+        let seeds = &[b"multisig_signer", multisig_key.as_ref(), &[multisig.signer_bump]];
+        invoke_signed(&ix, ctx.remaining_accounts, &[&seeds[..]])?;
+
+        emit!(ProposalExecuted {
+            multisig: multisig_key,
+            id: proposal.id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Replaces the signer set; only callable by the multisig itself through a
+    /// proposal. Pending proposals are invalidated.
+    pub fn set_multisig_signers(ctx: Context<SetMultisigSigners>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        check_multisig_signers(&signers, threshold)?;
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+        multisig.owner_set_seqno = multisig.owner_set_seqno.wrapping_add(1);
+
+        emit!(MultisigSignersChanged {
+            multisig: multisig.key(),
+            signers: multisig.signers.clone(),
+            threshold,
+        });
         Ok(())
     }
 
@@ -31,316 +451,655 @@ pub mod marscorp_unified {
     // TOKEN LAUNCHPAD (BONDING CURVE)
     // ═══════════════════════════════════════════════════════════════════════
 
+    /// Launches a business token on a fresh bonding curve. If
+    /// `initial_buy_lamports` is set, the creator's first buy is executed in the
+    /// same instruction at the fresh-curve price with normal fees, including
+    /// the launch-window fee and buy limits that apply to `swap`.
     pub fn create_business(
         ctx: Context<LaunchBusiness>, 
         name: String,
         symbol: String,
-        _uri: String,
-        sector: Sector
+        uri: String,
+        sector: Sector,
+        initial_buy_lamports: Option<u64>,
+        min_tokens_out: u64
     ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_LAUNCH)?;
         // Validation
-        require!(name.len() > 0 && name.len() < 50, UnifiedError::InvalidInput);
-        require!(symbol.len() > 0 && symbol.len() < 10, UnifiedError::InvalidInput);
+        require!(!name.is_empty() && name.len() < 50, UnifiedError::InvalidInput);
+        require!(!symbol.is_empty() && symbol.len() < 10, UnifiedError::InvalidInput);
+        require!(uri.len() <= MAX_URI_LENGTH, UnifiedError::InvalidInput);
+        let quote_asset = launch_quote_asset(&ctx.accounts.config, ctx.accounts.quote_mint.as_ref())?;
 
-        // 1. Initialize Bonding Curve State
-        let curve = &mut ctx.accounts.curve;
-        curve.creator = ctx.accounts.creator.key();
-        curve.mint = ctx.accounts.mint.key();
-        curve.sector = sector;
-        curve.virtual_sol = 30_000_000_000; // 30 SOL initial virtual liquidity
-        curve.virtual_tokens = 1_073_000_000_000_000; // ~1B tokens
-        curve.real_sol = 0;
-        curve.graduated = false;
-        curve.takeover_active = false;
-        curve.takeover_initiator = Pubkey::default();
-        curve.sabotage_penalty_bps = 0;
-        curve.sabotage_end_ts = 0;
-        curve.bump = ctx.bumps.curve;
-
-        // 2. Initialize Vesting Account (20% Allocation)
-        let vesting = &mut ctx.accounts.vesting;
-        vesting.owner = ctx.accounts.creator.key();
-        vesting.mint = ctx.accounts.mint.key();
-        vesting.total_amount = 200_000_000_000_000; // 200M tokens
-        vesting.released_amount = 0;
-        vesting.start_ts = Clock::get()?.unix_timestamp;
-        vesting.end_ts = vesting.start_ts
-            .checked_add(365 * 24 * 60 * 60)
-            .ok_or(UnifiedError::Overflow)?;
-        vesting.bump = ctx.bumps.vesting;
+        // 1. Initialize Curve, Vesting (20% Allocation) and Creator Fee Vault
+        init_launch_state(
+            &mut ctx.accounts.curve,
+            &mut ctx.accounts.vesting,
+            &mut ctx.accounts.creator_fee_vault,
+            &mut ctx.accounts.yield_vault,
+            ctx.accounts.creator.key(),
+            ctx.accounts.mint.key(),
+            sector,
+            &quote_asset,
+        )?;
+        ctx.accounts.curve.bump = ctx.bumps.curve;
+        ctx.accounts.vesting.bump = ctx.bumps.vesting;
+        ctx.accounts.creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+        ctx.accounts.yield_vault.bump = ctx.bumps.yield_vault;
 
-        // 3. Mint Tokens
+        // 2. Mint Tokens
+        let curve = &ctx.accounts.curve;
         let mint_key = ctx.accounts.mint.key();
         let seeds = &[b"curve", mint_key.as_ref(), &[curve.bump]];
         let signer = &[&seeds[..]];
 
-        // Mint 80% to Curve (Trading Supply)
-        token::mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.curve_token_vault.to_account_info(),
-                    authority: curve.to_account_info(),
-                },
-                signer
-            ),
-            800_000_000_000_000 // 800M
+        mint_launch_supply(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.curve_token_vault.to_account_info(),
+            ctx.accounts.vesting_token_vault.to_account_info(),
+            curve.to_account_info(),
+            signer,
         )?;
 
-        // Mint 20% to Vesting Vault (Locked for Creator)
-        token::mint_to(
+        // Token metadata, updatable by the curve on the creator's behalf
+        metadata::create_metadata_accounts_v3(
             CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
                     mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.vesting_token_vault.to_account_info(),
-                    authority: curve.to_account_info(),
+                    mint_authority: curve.to_account_info(),
+                    payer: ctx.accounts.creator.to_account_info(),
+                    update_authority: curve.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
                 },
                 signer
             ),
-            200_000_000_000_000 // 200M
+            business_metadata(name.clone(), symbol.clone(), uri),
+            true,
+            true,
+            None
         )?;
 
+        let referrer = attribute_launch(ctx.accounts.referrer_account.as_mut(), ctx.accounts.creator.key())?;
+
+        // 3. Creator Pre-Buy, under the same launch-window fee and limits as `swap`
+        let mut initial_buy = 0u64;
+        let mut initial_tokens = 0u64;
+        if let Some(lamports) = initial_buy_lamports {
+            require!(lamports > 0, UnifiedError::InvalidInput);
+            // SPL-quoted launches pre-buy with a `swap` in the same transaction
+            check_sol_quoted(&ctx.accounts.curve)?;
+            let accounts = &mut *ctx.accounts;
+            let creator_token_account = accounts.creator_token_account.as_ref()
+                .ok_or(UnifiedError::InvalidInput)?;
+
+            let now = Clock::get()?.unix_timestamp;
+            let fees = with_launch_fee(fee_schedule(&accounts.config), &accounts.config, &accounts.curve, true, now);
+            let quote = quote_exact_in(&fees, &accounts.curve, true, lamports, now)?;
+            require!(quote.amount_out >= min_tokens_out, UnifiedError::SlippageExceeded);
+            check_launch_limits(
+                &accounts.config,
+                &accounts.curve,
+                accounts.launch_allocation.as_mut(),
+                ctx.bumps.launch_allocation,
+                accounts.creator.key(),
+                quote.amount_in,
+            )?;
+            initial_buy = quote.amount_in;
+            initial_tokens = quote.amount_out;
+
+            let routed = record_trade(
+                &accounts.config,
+                &mut accounts.curve,
+                &mut accounts.creator_fee_vault,
+                &mut accounts.yield_vault,
+                accounts.referrer_account.as_mut(),
+                accounts.creator.key(),
+                true,
+                &quote,
+                0,
+            )?;
+
+            TradeTransfer {
+                user: accounts.creator.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                decimals: accounts.mint.decimals,
+                user_token_account: creator_token_account.to_account_info(),
+                curve: accounts.curve.to_account_info(),
+                curve_token_vault: accounts.curve_token_vault.to_account_info(),
+                admin_treasury: accounts.admin_treasury.to_account_info(),
+                yield_vault: accounts.yield_vault.to_account_info(),
+                creator_fee_vault: accounts.creator_fee_vault.to_account_info(),
+                referrer: accounts.referrer_account.as_ref().map(|r| r.to_account_info()),
+                token_program: accounts.token_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                quote: None,
+            }.execute(&accounts.curve, true, &quote, &routed)?;
+        }
+
         emit!(BusinessLaunched {
             mint: ctx.accounts.mint.key(),
             name,
             symbol,
             creator: ctx.accounts.creator.key(),
+            referrer,
+            quote_mint: quote_asset.mint,
+            initial_buy_lamports: initial_buy,
+            initial_tokens,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    pub fn swap(ctx: Context<Swap>, is_buy: bool, amount: u64, min_out: u64) -> Result<()> {
-        let config = &ctx.accounts.config;
-        let curve = &mut ctx.accounts.curve;
-        
-        require!(!curve.graduated, UnifiedError::AlreadyGraduated);
-        require!(amount > 0, UnifiedError::InvalidInput);
+    /// Token-2022 launch path. The mint carries a transfer fee on every
+    /// transfer, withdrawable by the yield distributor so it reaches holders,
+    /// and keeps its metadata on the mint through the metadata-pointer
+    /// extension. Both are fixed at launch. There is no built-in pre-buy; a
+    /// `swap` in the same transaction serves that purpose.
+    pub fn create_business_2022(
+        ctx: Context<LaunchBusiness2022>,
+        name: String,
+        symbol: String,
+        uri: String,
+        sector: Sector,
+        transfer_fee_bps: u16
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_LAUNCH)?;
+        require!(!name.is_empty() && name.len() < 50, UnifiedError::InvalidInput);
+        require!(!symbol.is_empty() && symbol.len() < 10, UnifiedError::InvalidInput);
+        require!(uri.len() <= MAX_URI_LENGTH, UnifiedError::InvalidInput);
+        require!(transfer_fee_bps <= MAX_TRANSFER_FEE_BPS, UnifiedError::FeeTooHigh);
+        let quote_asset = launch_quote_asset(&ctx.accounts.config, ctx.accounts.quote_mint.as_ref())?;
 
-        // Apply sabotage penalty if active
-        let current_time = Clock::get()?.unix_timestamp;
-        let penalty_active = curve.sabotage_end_ts > current_time;
-        let penalty_multiplier = if penalty_active {
-            10000u128 - curve.sabotage_penalty_bps as u128 // e.g., 9900 if 1% penalty
-        } else {
-            10000u128
+        let accounts = &ctx.accounts;
+        let creator_key = accounts.creator.key();
+        let mint_key = accounts.mint.key();
+        let curve_key = accounts.curve.key();
+        let token_program = accounts.token_program.to_account_info();
+        let rent = Rent::get()?;
+
+        let mint_seeds = &[b"mint", creator_key.as_ref(), symbol.as_bytes(), &[ctx.bumps.mint]];
+        let curve_seeds = &[b"curve", mint_key.as_ref(), &[ctx.bumps.curve]];
+        let vault_seeds = &[b"vesting_vault", mint_key.as_ref(), &[ctx.bumps.vesting_token_vault]];
+        let curve_signer = &[&curve_seeds[..]];
+
+        // 1. Create the mint with its extensions
+        let mint_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MetadataPointer,
+        ])?;
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(curve_key))?,
+            mint: mint_key,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![],
         };
+        // The metadata initialization reallocs the mint, so fund its final size up front
+        let mint_lamports = rent.minimum_balance(mint_space + token_metadata.tlv_size_of()?);
 
-        // --- CALCULATIONS ---
-        let amount_u128 = amount as u128;
-        let platform_fee_bps = config.platform_fee_bps as u128;
-        let yield_fee_bps = config.yield_fee_bps as u128;
+        invoke_signed(
+            &system_instruction::create_account(
+                &creator_key, &mint_key, mint_lamports, mint_space as u64, &token_2022::ID
+            ),
+            &[accounts.creator.to_account_info(), accounts.mint.to_account_info(),
+              accounts.system_program.to_account_info()],
+            &[&mint_seeds[..]]
+        )?;
 
-        let tokens_out_u64: u64;
-        let sol_amount_to_curve: u64;
-        let sol_out_net_u64: u64;
-        let platform_fee_u64: u64;
-        let yield_fee_u64: u64;
+        token_interface::transfer_fee_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                TransferFeeInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: accounts.mint.to_account_info(),
+                }
+            ),
+            None,
+            Some(&accounts.config.yield_distributor),
+            transfer_fee_bps,
+            u64::MAX
+        )?;
 
-        if is_buy {
-            // BUY: SOL In -> Tokens Out
-            let platform_fee = amount_u128.checked_mul(platform_fee_bps).unwrap().checked_div(10000).unwrap();
-            let yield_fee = amount_u128.checked_mul(yield_fee_bps).unwrap().checked_div(10000).unwrap();
-            let total_fee = platform_fee.checked_add(yield_fee).ok_or(UnifiedError::Overflow)?;
-            let amount_after_fee = amount_u128.checked_sub(total_fee).ok_or(UnifiedError::Overflow)?;
-
-            // Bonding Curve Math
-            let x = curve.virtual_sol;
-            let y = curve.virtual_tokens;
-            let k = x.checked_mul(y).ok_or(UnifiedError::Overflow)?;
-            
-            let new_x = x.checked_add(amount_after_fee).ok_or(UnifiedError::Overflow)?;
-            let new_y = k.checked_div(new_x).ok_or(UnifiedError::Overflow)?;
-            let tokens_out = y.checked_sub(new_y).ok_or(UnifiedError::Overflow)?;
+        token_interface::metadata_pointer_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: accounts.mint.to_account_info(),
+                }
+            ),
+            Some(curve_key),
+            Some(mint_key)
+        )?;
 
-            // Apply sabotage penalty to output
-            let tokens_out_adjusted = tokens_out.checked_mul(penalty_multiplier).unwrap()
-                .checked_div(10000).ok_or(UnifiedError::Overflow)?;
-            
-            if tokens_out_adjusted > u64::MAX as u128 { 
-                return err!(UnifiedError::Overflow); 
-            }
-            tokens_out_u64 = tokens_out_adjusted as u64;
-            
-            require!(tokens_out_u64 >= min_out, UnifiedError::SlippageExceeded);
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                token_program.clone(),
+                InitializeMint2 { mint: accounts.mint.to_account_info() }
+            ),
+            6,
+            &curve_key,
+            None
+        )?;
 
-            // STATE UPDATE
-            curve.virtual_sol = new_x;
-            curve.virtual_tokens = new_y;
-            curve.real_sol = curve.real_sol.checked_add(amount_after_fee as u64)
-                .ok_or(UnifiedError::Overflow)?;
+        token_interface::token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: token_program.clone(),
+                    metadata: accounts.mint.to_account_info(),
+                    update_authority: accounts.curve.to_account_info(),
+                    mint_authority: accounts.curve.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                },
+                curve_signer
+            ),
+            name.clone(),
+            symbol.clone(),
+            uri
+        )?;
 
-            platform_fee_u64 = platform_fee as u64;
-            yield_fee_u64 = yield_fee as u64;
-            sol_amount_to_curve = amount_after_fee as u64;
-            sol_out_net_u64 = 0;
+        // 2. Create the curve and vesting vaults
+        associated_token::create(
+            CpiContext::new(
+                accounts.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: accounts.creator.to_account_info(),
+                    associated_token: accounts.curve_token_vault.to_account_info(),
+                    authority: accounts.curve.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    token_program: token_program.clone(),
+                }
+            )
+        )?;
 
-        } else {
-            // SELL: Tokens In -> SOL Out
-            let x = curve.virtual_sol;
-            let y = curve.virtual_tokens;
-            let k = x.checked_mul(y).ok_or(UnifiedError::Overflow)?;
-            
-            let new_y = y.checked_add(amount_u128).ok_or(UnifiedError::Overflow)?;
-            let new_x = k.checked_div(new_y).ok_or(UnifiedError::Overflow)?;
-            let sol_out_gross = x.checked_sub(new_x).ok_or(UnifiedError::Overflow)?;
+        let vault_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &ExtensionType::get_required_init_account_extensions(&[ExtensionType::TransferFeeConfig])
+        )?;
+        invoke_signed(
+            &system_instruction::create_account(
+                &creator_key,
+                &accounts.vesting_token_vault.key(),
+                rent.minimum_balance(vault_space),
+                vault_space as u64,
+                &token_2022::ID
+            ),
+            &[accounts.creator.to_account_info(), accounts.vesting_token_vault.to_account_info(),
+              accounts.system_program.to_account_info()],
+            &[&vault_seeds[..]]
+        )?;
 
-            // Apply sabotage penalty to output (Simulates slippage or "tax" from economic damage)
-            let sol_out_gross_adjusted = sol_out_gross.checked_mul(penalty_multiplier).unwrap()
-                .checked_div(10000).ok_or(UnifiedError::Overflow)?;
-            
-            // Calculate fees from adjusted output
-            let platform_fee = sol_out_gross_adjusted.checked_mul(platform_fee_bps).unwrap()
-                .checked_div(10000).unwrap();
-            let yield_fee = sol_out_gross_adjusted.checked_mul(yield_fee_bps).unwrap()
-                .checked_div(10000).unwrap();
-            let total_fee = platform_fee.checked_add(yield_fee).ok_or(UnifiedError::Overflow)?;
-            let sol_out_net = sol_out_gross_adjusted.checked_sub(total_fee)
-                .ok_or(UnifiedError::Overflow)?;
-
-            if sol_out_net > u64::MAX as u128 { 
-                return err!(UnifiedError::Overflow); 
-            }
-            sol_out_net_u64 = sol_out_net as u64;
-            
-            require!(sol_out_net_u64 >= min_out, UnifiedError::SlippageExceeded);
+        token_interface::initialize_account3(
+            CpiContext::new(
+                token_program.clone(),
+                InitializeAccount3 {
+                    account: accounts.vesting_token_vault.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    authority: accounts.curve.to_account_info(),
+                }
+            )
+        )?;
 
-            // STATE UPDATE
-            curve.virtual_sol = new_x;
-            curve.virtual_tokens = new_y;
-            
-            let sol_out_gross_u64 = sol_out_gross_adjusted as u64;
-            // Reduce Real SOL by GROSS amount (what leaves the bonding curve)
-            curve.real_sol = curve.real_sol.checked_sub(sol_out_gross_u64)
-                .ok_or(UnifiedError::Overflow)?;
-
-            platform_fee_u64 = platform_fee as u64;
-            yield_fee_u64 = yield_fee as u64;
-            sol_amount_to_curve = 0;
-            tokens_out_u64 = 0;
-        }
-
-        // Graduation Check
-        if curve.real_sol > 85_000_000_000 && !curve.graduated {
-            curve.graduated = true;
-            emit!(GraduationReady {
-                mint: curve.mint,
-                sol_amount: curve.real_sol,
-                token_amount: curve.virtual_tokens as u64,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
-        }
+        // 3. Initialize Curve, Vesting and Creator Fee Vault, then mint the supply
+        let accounts = &mut *ctx.accounts;
+        init_launch_state(
+            &mut accounts.curve,
+            &mut accounts.vesting,
+            &mut accounts.creator_fee_vault,
+            &mut accounts.yield_vault,
+            creator_key,
+            mint_key,
+            sector,
+            &quote_asset,
+        )?;
+        accounts.curve.bump = ctx.bumps.curve;
+        accounts.vesting.bump = ctx.bumps.vesting;
+        accounts.creator_fee_vault.bump = ctx.bumps.creator_fee_vault;
+        accounts.yield_vault.bump = ctx.bumps.yield_vault;
 
-        emit!(PriceUpdated {
-            mint: curve.mint,
-            sol_reserves: curve.virtual_sol as u64,
-            token_reserves: curve.virtual_tokens as u64,
+        mint_launch_supply(
+            token_program,
+            accounts.mint.to_account_info(),
+            accounts.curve_token_vault.to_account_info(),
+            accounts.vesting_token_vault.to_account_info(),
+            accounts.curve.to_account_info(),
+            curve_signer,
+        )?;
+
+        let referrer = attribute_launch(accounts.referrer_account.as_mut(), creator_key)?;
+
+        emit!(BusinessLaunched {
+            mint: mint_key,
+            name,
+            symbol,
+            creator: creator_key,
+            referrer,
+            quote_mint: quote_asset.mint,
+            initial_buy_lamports: 0,
+            initial_tokens: 0,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        // --- EXTERNAL INTERACTIONS ---
-        
-        if is_buy {
-            // Pay fees
-            if platform_fee_u64 > 0 {
-                invoke(
-                    &system_instruction::transfer(&ctx.accounts.user.key(), &config.admin, platform_fee_u64),
-                    &[ctx.accounts.user.to_account_info(), ctx.accounts.admin_treasury.to_account_info(), 
-                      ctx.accounts.system_program.to_account_info()],
-                )?;
-            }
-            if yield_fee_u64 > 0 {
-                invoke(
-                    &system_instruction::transfer(&ctx.accounts.user.key(), &config.yield_distributor, yield_fee_u64),
-                    &[ctx.accounts.user.to_account_info(), ctx.accounts.yield_distributor.to_account_info(), 
-                      ctx.accounts.system_program.to_account_info()],
-                )?;
-            }
+        Ok(())
+    }
 
-            // Transfer SOL to curve
-            invoke(
-                &system_instruction::transfer(&ctx.accounts.user.key(), &curve.key(), sol_amount_to_curve),
-                &[ctx.accounts.user.to_account_info(), curve.to_account_info(), 
-                  ctx.accounts.system_program.to_account_info()],
-            )?;
+    /// Lets the curve's current creator update the token's on-chain metadata:
+    /// the Metaplex account of a `create_business` launch, or the metadata
+    /// extension in the mint of a `create_business_2022` launch.
+    pub fn update_business_metadata(
+        ctx: Context<UpdateBusinessMetadata>,
+        name: String,
+        symbol: String,
+        uri: String
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_LAUNCH)?;
+        require!(!name.is_empty() && name.len() < 50, UnifiedError::InvalidInput);
+        require!(!symbol.is_empty() && symbol.len() < 10, UnifiedError::InvalidInput);
+        require!(uri.len() <= MAX_URI_LENGTH, UnifiedError::InvalidInput);
 
-            // Transfer tokens to user
-            let seeds = &[b"curve", curve.mint.as_ref(), &[curve.bump]];
-            let signer = &[&seeds[..]];
-            token::transfer(
+        let curve = &ctx.accounts.curve;
+        let seeds = &[b"curve", curve.mint.as_ref(), &[curve.bump]];
+        let signer = &[&seeds[..]];
+
+        if *ctx.accounts.mint.to_account_info().owner == token_2022::ID {
+            update_token_2022_metadata(ctx.accounts, signer, &name, &symbol, &uri)?;
+        } else {
+            let (Some(metadata_account), Some(token_metadata_program)) =
+                (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program)
+            else {
+                return err!(UnifiedError::InvalidInput);
+            };
+            metadata::update_metadata_accounts_v2(
                 CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.curve_token_vault.to_account_info(),
-                        to: ctx.accounts.user_token_account.to_account_info(),
-                        authority: curve.to_account_info(),
+                    token_metadata_program.to_account_info(),
+                    UpdateMetadataAccountsV2 {
+                        metadata: metadata_account.to_account_info(),
+                        update_authority: curve.to_account_info(),
                     },
                     signer
                 ),
-                tokens_out_u64
-            )?;
-
-        } else {
-            // Transfer tokens to curve
-            token::transfer(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.user_token_account.to_account_info(),
-                        to: ctx.accounts.curve_token_vault.to_account_info(),
-                        authority: ctx.accounts.user.to_account_info(),
-                    }
-                ),
-                amount
+                None,
+                Some(business_metadata(name.clone(), symbol.clone(), uri.clone())),
+                None,
+                None
             )?;
-
-            // Pay out SOL
-            let seeds = &[b"curve", curve.mint.as_ref(), &[curve.bump]];
-            let signer = &[&seeds[..]];
-
-            if platform_fee_u64 > 0 {
-                invoke_signed(
-                    &system_instruction::transfer(&curve.key(), &config.admin, platform_fee_u64),
-                    &[curve.to_account_info(), ctx.accounts.admin_treasury.to_account_info(), 
-                      ctx.accounts.system_program.to_account_info()],
-                    signer
-                )?;
-            }
-            if yield_fee_u64 > 0 {
-                invoke_signed(
-                    &system_instruction::transfer(&curve.key(), &config.yield_distributor, yield_fee_u64),
-                    &[curve.to_account_info(), ctx.accounts.yield_distributor.to_account_info(), 
-                      ctx.accounts.system_program.to_account_info()],
-                    signer
-                )?;
-            }
-            if sol_out_net_u64 > 0 {
-                invoke_signed(
-                    &system_instruction::transfer(&curve.key(), &ctx.accounts.user.key(), sol_out_net_u64),
-                    &[curve.to_account_info(), ctx.accounts.user.to_account_info(), 
-                      ctx.accounts.system_program.to_account_info()],
-                    signer
-                )?;
-            }
         }
 
+        emit!(BusinessMetadataUpdated {
+            mint: curve.mint,
+            name,
+            symbol,
+            uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
         Ok(())
     }
 
-    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
-        let vesting = &mut ctx.accounts.vesting;
-        require!(ctx.accounts.owner.key() == vesting.owner, UnifiedError::Unauthorized);
+    /// Exact-input swap. `deadline_ts` and `max_price_impact_bps` are optional
+    /// guards on top of `min_out`. On Token-2022 mints with a transfer fee,
+    /// `amount` of a sell is what the curve receives (the trader sends it plus
+    /// the fee) and `min_out` of a buy applies to what the trader receives.
+    pub fn swap(
+        ctx: Context<Swap>,
+        is_buy: bool,
+        amount: u64,
+        min_out: u64,
+        deadline_ts: Option<i64>,
+        max_price_impact_bps: Option<u16>
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        require!(!ctx.accounts.curve.graduated, UnifiedError::AlreadyGraduated);
+        require!(amount > 0, UnifiedError::InvalidInput);
 
         let now = Clock::get()?.unix_timestamp;
-        require!(now >= vesting.start_ts, UnifiedError::InvalidTime);
-        
-        let total_duration = vesting.end_ts.checked_sub(vesting.start_ts)
-            .ok_or(UnifiedError::Overflow)?;
-        require!(total_duration > 0, UnifiedError::InvalidTime);
-        
+        check_deadline(deadline_ts, now)?;
+        let (fees, fee_tier) = trader_fees(
+            &ctx.accounts.config, &ctx.accounts.mars_holder_account, &ctx.accounts.mars_stake_position,
+            &ctx.accounts.user.key()
+        )?;
+        let fees = with_launch_fee(fees, &ctx.accounts.config, &ctx.accounts.curve, is_buy, now);
+        let quote = quote_exact_in(&fees, &ctx.accounts.curve, is_buy, amount, now)?;
+        // A buy capped at graduation only has to honour min_out for the part that was filled.
+        let min_out = if quote.refund > 0 {
+            math::mul_div(min_out, quote.amount_in, amount).map_err(UnifiedError::from)?
+        } else {
+            min_out
+        };
+        let received = if is_buy {
+            let mint = ctx.accounts.mint.to_account_info();
+            quote.amount_out - transfer_fee(&mint, quote.amount_out)?
+        } else {
+            quote.amount_out
+        };
+        require!(received >= min_out, UnifiedError::SlippageExceeded);
+        check_price_impact(&ctx.accounts.curve, &quote, max_price_impact_bps)?;
+
+        settle_swap(ctx.accounts, &ctx.bumps, is_buy, &quote, fee_tier)
+    }
+
+    /// Exact-output variant of `swap`: the trader receives exactly `amount_out`
+    /// (tokens on a buy, lamports on a sell) and pays at most `max_in`, with
+    /// any Token-2022 transfer fee included on both sides.
+    pub fn swap_exact_out(
+        ctx: Context<Swap>,
+        is_buy: bool,
+        amount_out: u64,
+        max_in: u64,
+        deadline_ts: Option<i64>,
+        max_price_impact_bps: Option<u16>
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        require!(!ctx.accounts.curve.graduated, UnifiedError::AlreadyGraduated);
+        require!(amount_out > 0, UnifiedError::InvalidInput);
+
+        let now = Clock::get()?.unix_timestamp;
+        check_deadline(deadline_ts, now)?;
+        let (fees, fee_tier) = trader_fees(
+            &ctx.accounts.config, &ctx.accounts.mars_holder_account, &ctx.accounts.mars_stake_position,
+            &ctx.accounts.user.key()
+        )?;
+        let fees = with_launch_fee(fees, &ctx.accounts.config, &ctx.accounts.curve, is_buy, now);
+        let mint = ctx.accounts.mint.to_account_info();
+        let amount_out = if is_buy { with_transfer_fee(&mint, amount_out)? } else { amount_out };
+        let quote = quote_exact_out(&fees, &ctx.accounts.curve, is_buy, amount_out, now)?;
+        let paid = if is_buy { quote.amount_in } else { with_transfer_fee(&mint, quote.amount_in)? };
+        require!(paid <= max_in, UnifiedError::SlippageExceeded);
+        check_price_impact(&ctx.accounts.curve, &quote, max_price_impact_bps)?;
+
+        settle_swap(ctx.accounts, &ctx.bumps, is_buy, &quote, fee_tier)
+    }
+
+    /// Sells `amount` of token A on its curve and buys token B with the SOL
+    /// proceeds on B's curve. Each leg pays the fees of a normal swap;
+    /// `min_out` applies to the B tokens the trader ends up with.
+    pub fn swap_business_to_business(
+        ctx: Context<SwapBusinessToBusiness>,
+        amount: u64,
+        min_out: u64,
+        deadline_ts: Option<i64>
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let accounts = &mut *ctx.accounts;
+        require!(!accounts.curve_in.graduated && !accounts.curve_out.graduated, UnifiedError::AlreadyGraduated);
+        require!(accounts.curve_in.mint != accounts.curve_out.mint, UnifiedError::InvalidInput);
+        check_sol_quoted(&accounts.curve_in)?;
+        check_sol_quoted(&accounts.curve_out)?;
+        require!(amount > 0, UnifiedError::InvalidInput);
+
+        let now = Clock::get()?.unix_timestamp;
+        check_deadline(deadline_ts, now)?;
+        let (fees, fee_tier) = trader_fees(
+            &accounts.config, &accounts.mars_holder_account, &accounts.mars_stake_position, &accounts.user.key()
+        )?;
+
+        // Price both legs up front; the buy spends exactly what the sell pays out
+        let sell = quote_exact_in(&fees, &accounts.curve_in, false, amount, now)?;
+        require!(sell.amount_out > 0, UnifiedError::InvalidInput);
+        let buy_fees = with_launch_fee(fees, &accounts.config, &accounts.curve_out, true, now);
+        let buy = quote_exact_in(&buy_fees, &accounts.curve_out, true, sell.amount_out, now)?;
+        let mint_out = accounts.mint_out.to_account_info();
+        let tokens_out = buy.amount_out - transfer_fee(&mint_out, buy.amount_out)?;
+        require!(tokens_out >= min_out, UnifiedError::SlippageExceeded);
+
+        check_launch_limits(
+            &accounts.config,
+            &accounts.curve_out,
+            accounts.launch_allocation.as_mut(),
+            ctx.bumps.launch_allocation,
+            accounts.user.key(),
+            buy.amount_in,
+        )?;
+
+        // Leg 1: sell A
+        let routed = record_trade(
+            &accounts.config,
+            &mut accounts.curve_in,
+            &mut accounts.creator_fee_vault_in,
+            &mut accounts.yield_vault_in,
+            accounts.referrer_account.as_mut(),
+            accounts.user.key(),
+            false,
+            &sell,
+            fee_tier,
+        )?;
+        TradeTransfer {
+            user: accounts.user.to_account_info(),
+            mint: accounts.mint_in.to_account_info(),
+            decimals: accounts.mint_in.decimals,
+            user_token_account: accounts.user_token_account_in.to_account_info(),
+            curve: accounts.curve_in.to_account_info(),
+            curve_token_vault: accounts.curve_token_vault_in.to_account_info(),
+            admin_treasury: accounts.admin_treasury.to_account_info(),
+            yield_vault: accounts.yield_vault_in.to_account_info(),
+            creator_fee_vault: accounts.creator_fee_vault_in.to_account_info(),
+            referrer: accounts.referrer_account.as_ref().map(|r| r.to_account_info()),
+            token_program: accounts.token_program_in.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            quote: None,
+        }.execute(&accounts.curve_in, false, &sell, &routed)?;
+
+        // Leg 2: buy B
+        let routed = record_trade(
+            &accounts.config,
+            &mut accounts.curve_out,
+            &mut accounts.creator_fee_vault_out,
+            &mut accounts.yield_vault_out,
+            accounts.referrer_account.as_mut(),
+            accounts.user.key(),
+            true,
+            &buy,
+            fee_tier,
+        )?;
+        TradeTransfer {
+            user: accounts.user.to_account_info(),
+            mint: mint_out,
+            decimals: accounts.mint_out.decimals,
+            user_token_account: accounts.user_token_account_out.to_account_info(),
+            curve: accounts.curve_out.to_account_info(),
+            curve_token_vault: accounts.curve_token_vault_out.to_account_info(),
+            admin_treasury: accounts.admin_treasury.to_account_info(),
+            yield_vault: accounts.yield_vault_out.to_account_info(),
+            creator_fee_vault: accounts.creator_fee_vault_out.to_account_info(),
+            referrer: accounts.referrer_account.as_ref().map(|r| r.to_account_info()),
+            token_program: accounts.token_program_out.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            quote: None,
+        }.execute(&accounts.curve_out, true, &buy, &routed)?;
+
+        emit!(BusinessToBusinessSwap {
+            trader: accounts.user.key(),
+            mint_in: accounts.curve_in.mint,
+            mint_out: accounts.curve_out.mint,
+            tokens_in: sell.amount_in,
+            sol_routed: buy.amount_in,
+            sol_refunded: buy.refund,
+            tokens_out,
+            total_fees: sell.total_fee() + buy.total_fee(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Read-only simulation of `swap` / `swap_exact_out`. Runs the same pricing
+    /// path, Token-2022 transfer fee included, without moving funds and returns
+    /// the result as return data. `amount_in` is what the trader sends and
+    /// `tokens_out` what they receive. Fails like `swap` while swaps are
+    /// paused or the curve is halted.
+    pub fn quote_swap(
+        ctx: Context<QuoteSwap>,
+        is_buy: bool,
+        amount: u64,
+        exact_out: bool
+    ) -> Result<SwapQuoteResult> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let curve = &ctx.accounts.curve;
+        require!(!curve.graduated, UnifiedError::AlreadyGraduated);
+        require!(amount > 0, UnifiedError::InvalidInput);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(!is_halted(curve, now), UnifiedError::TradingHalted);
+        let (fees, fee_tier) = trader_fees(
+            &ctx.accounts.config, &ctx.accounts.mars_holder_account, &ctx.accounts.mars_stake_position,
+            &ctx.accounts.trader.key()
+        )?;
+        let fees = with_launch_fee(fees, &ctx.accounts.config, curve, is_buy, now);
+        let mint = ctx.accounts.mint.to_account_info();
+        let quote = if exact_out {
+            let amount = if is_buy { with_transfer_fee(&mint, amount)? } else { amount };
+            quote_exact_out(&fees, curve, is_buy, amount, now)?
+        } else {
+            quote_exact_in(&fees, curve, is_buy, amount, now)?
+        };
+        let price_impact_bps = math::price_impact_bps(&curve_reserves(curve), &quote.reserves)
+            .map_err(UnifiedError::from)?;
+
+        // Token-2022 transfer fee on the token leg, as `swap` applies it
+        let (amount_in, tokens_out, sol_out, token_transfer_fee) = if is_buy {
+            let fee = transfer_fee(&mint, quote.amount_out)?;
+            (quote.amount_in, quote.amount_out - fee, 0, fee)
+        } else {
+            let sent = with_transfer_fee(&mint, quote.amount_in)?;
+            (sent, 0, quote.amount_out, sent - quote.amount_in)
+        };
+        Ok(SwapQuoteResult {
+            amount_in,
+            tokens_out,
+            sol_out,
+            platform_fee: quote.platform_fee,
+            yield_fee: quote.yield_fee,
+            creator_fee: quote.creator_fee,
+            penalty: quote.penalty,
+            refund: quote.refund,
+            token_transfer_fee,
+            fee_tier,
+            price_impact_bps,
+            virtual_sol: quote.reserves.virtual_sol,
+            virtual_tokens: quote.reserves.virtual_tokens,
+            real_sol: quote.reserves.real_sol,
+        })
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let vesting = &mut ctx.accounts.vesting;
+        require!(ctx.accounts.owner.key() == vesting.owner, UnifiedError::Unauthorized);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= vesting.start_ts, UnifiedError::InvalidTime);
+        
+        let total_duration = vesting.end_ts.checked_sub(vesting.start_ts)
+            .ok_or(UnifiedError::Overflow)?;
+        require!(total_duration > 0, UnifiedError::InvalidTime);
+        
         let elapsed = now.checked_sub(vesting.start_ts).ok_or(UnifiedError::Overflow)?;
         
         let vested_amount: u64 = if now >= vesting.end_ts {
@@ -357,7 +1116,7 @@ pub mod marscorp_unified {
             vested_u128 as u64
         };
 
-        let claimable = vested_amount.checked_sub(vesting.released_amount).unwrap_or(0);
+        let claimable = vested_amount.saturating_sub(vesting.released_amount);
         require!(claimable > 0, UnifiedError::NothingToClaim);
 
         // Transfer
@@ -365,17 +1124,19 @@ pub mod marscorp_unified {
         let seeds = &[b"curve", vesting.mint.as_ref(), &[curve_bump]];
         let signer = &[&seeds[..]];
 
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.vesting_token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.owner_token_account.to_account_info(),
                     authority: ctx.accounts.curve.to_account_info(),
                 },
                 signer
             ),
-            claimable
+            claimable,
+            ctx.accounts.mint.decimals
         )?;
 
         vesting.released_amount = vesting.released_amount.checked_add(claimable)
@@ -384,312 +1145,3823 @@ pub mod marscorp_unified {
         Ok(())
     }
 
-    // ═══════════════════════════════════════════════════════════════════════
-    // GAME MECHANICS
-    // ═══════════════════════════════════════════════════════════════════════
+    /// Pays out creator fees accrued from trading to the curve's current creator.
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let vault = &mut ctx.accounts.creator_fee_vault;
+        let amount = vault.pending;
+        require!(amount > 0, UnifiedError::NothingToClaim);
 
-    pub fn initiate_takeover(ctx: Context<InitiateTakeover>) -> Result<()> {
-        let curve = &mut ctx.accounts.curve;
-        
-        require!(!curve.takeover_active, UnifiedError::TakeoverInProgress);
-        require!(!curve.graduated, UnifiedError::AlreadyGraduated);
-        
-        // Must own 5% of circulating supply (800M tokens = 80% of 1B)
-        let circulating_supply = 800_000_000_000_000u64;
-        let required_stake = circulating_supply / 20; // 5%
-        
-        require!(
-            ctx.accounts.user_token_account.amount >= required_stake,
-            UnifiedError::InsufficientStakeForTakeover
-        );
+        vault.pending = 0;
+        vault.total_claimed = vault.total_claimed.checked_add(amount)
+            .ok_or(UnifiedError::Overflow)?;
 
-        // Activate takeover
-        curve.takeover_active = true;
-        curve.takeover_initiator = ctx.accounts.user.key();
+        let seeds = &[b"creator_vault", vault.mint.as_ref(), &[vault.bump]];
+        let signer = &[&seeds[..]];
+        if ctx.accounts.curve.quote_mint == Pubkey::default() {
+            transfer_lamports(&vault.to_account_info(), &ctx.accounts.creator.to_account_info(), amount)?;
+        } else {
+            // SPL-quoted curves accrue creator fees in the vault's quote token account
+            let (Some(quote_mint), Some(from), Some(to), Some(quote_token_program)) = (
+                ctx.accounts.quote_mint.as_ref(),
+                ctx.accounts.creator_fee_quote_vault.as_ref(),
+                ctx.accounts.creator_quote_account.as_ref(),
+                ctx.accounts.quote_token_program.as_ref(),
+            ) else {
+                return err!(UnifiedError::InvalidInput);
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    quote_token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: quote_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: vault.to_account_info(),
+                    },
+                    signer
+                ),
+                amount,
+                quote_mint.decimals
+            )?;
+        }
 
-        emit!(TakeoverInitiated {
-            mint: curve.mint,
-            initiator: ctx.accounts.user.key(),
-            stake: ctx.accounts.user_token_account.amount,
+        emit!(CreatorFeesClaimed {
+            mint: vault.mint,
+            creator: ctx.accounts.creator.key(),
+            amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    pub fn sabotage(ctx: Context<Sabotage>) -> Result<()> {
-        let config = &ctx.accounts.config;
-        let curve = &mut ctx.accounts.curve;
-        
-        // Cost: 2 SOL
-        let cost_lamports = 2_000_000_000u64;
-        
-        // Distribution:
-        // 50% -> Curve PDA (benefits token holders)
-        // 30% -> Admin treasury
-        // 20% -> Yield distributor
-        let to_curve = cost_lamports / 2;
-        let to_admin = (cost_lamports * 3) / 10;
-        let to_yield = cost_lamports - to_curve - to_admin;
+    // ═══════════════════════════════════════════════════════════════════════
+    // YIELD VAULTS
+    // ═══════════════════════════════════════════════════════════════════════
 
-        // Transfer sabotage fee
-        invoke(
-            &system_instruction::transfer(&ctx.accounts.user.key(), &curve.key(), to_curve),
-            &[ctx.accounts.user.to_account_info(), curve.to_account_info(), 
-              ctx.accounts.system_program.to_account_info()],
-        )?;
-        
-        invoke(
-            &system_instruction::transfer(&ctx.accounts.user.key(), &config.admin, to_admin),
-            &[ctx.accounts.user.to_account_info(), ctx.accounts.admin_treasury.to_account_info(), 
-              ctx.accounts.system_program.to_account_info()],
-        )?;
-        
-        invoke(
-            &system_instruction::transfer(&ctx.accounts.user.key(), &config.yield_distributor, to_yield),
-            &[ctx.accounts.user.to_account_info(), ctx.accounts.yield_distributor.to_account_info(), 
-              ctx.accounts.system_program.to_account_info()],
+    /// Creates the yield vault of a curve launched before yield vaults existed;
+    /// trading needs it. The curve has to be on the current layout
+    /// (`migrate_curve`). Permissionless, the caller pays the rent.
+    pub fn init_yield_vault(ctx: Context<InitYieldVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.yield_vault;
+        vault.mint = ctx.accounts.curve.mint;
+        vault.total_staked = 0;
+        vault.total_weight = 0;
+        vault.reward_index = 0;
+        vault.undistributed = 0;
+        vault.total_accrued = 0;
+        vault.total_claimed = 0;
+        vault.stream_updated_at = Clock::get()?.unix_timestamp;
+        vault.bump = ctx.bumps.yield_vault;
+        Ok(())
+    }
+
+    /// Stakes business tokens in the mint's yield vault. Staked balances earn a
+    /// share of every yield fee and creator deposit on the mint from now on,
+    /// weighted by the multiplier of `lock_tier` (see `STAKE_LOCK_TIERS`).
+    /// The whole position shares one lock. While it runs, a deposit has to pick
+    /// a tier with at least the lock's multiplier; the position then takes that
+    /// tier and its lock runs until the later of its current end and the new
+    /// tier's duration from now. A deposit without a lock never extends one.
+    pub fn stake_yield(ctx: Context<ModifyYieldStake>, amount: u64, lock_tier: u8) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_STAKING)?;
+        require!(amount > 0, UnifiedError::InvalidInput);
+        let (lock_secs, multiplier_bps) = *STAKE_LOCK_TIERS
+            .get(lock_tier as usize)
+            .ok_or(UnifiedError::InvalidInput)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        let accounts = &mut *ctx.accounts;
+        let position = &mut accounts.position;
+        if position.owner == Pubkey::default() {
+            position.owner = accounts.owner.key();
+            position.mint = accounts.mint.key();
+            position.multiplier_bps = STAKE_LOCK_TIERS[0].1;
+            position.lock_tier = 0;
+            position.bump = ctx.bumps.position;
+        }
+        checkpoint_position(&mut accounts.yield_vault, position)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.owner_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.stake_vault.to_account_info(),
+                    authority: accounts.owner.to_account_info(),
+                }
+            ),
+            amount,
+            accounts.mint.decimals
         )?;
+        // Only what reaches the vault after any transfer fee is credited
+        let credited = amount - transfer_fee(&accounts.mint.to_account_info(), amount)?;
+        position.staked = position.staked.checked_add(credited).ok_or(UnifiedError::Overflow)?;
+        let lock_end = now.checked_add(lock_secs).ok_or(UnifiedError::Overflow)?;
+        if now < position.lock_end {
+            // New tokens share the running lock's multiplier, so they must
+            // commit to at least its tier, and may not dilute it to a lower one
+            require!(multiplier_bps >= position.multiplier_bps, UnifiedError::LockTierDowngrade);
+            position.lock_end = position.lock_end.max(lock_end);
+            position.lock_tier = lock_tier;
+            position.multiplier_bps = multiplier_bps;
+        } else if lock_secs > 0 {
+            position.lock_end = lock_end;
+            position.lock_tier = lock_tier;
+            position.multiplier_bps = multiplier_bps;
+        }
+        let vault = &mut accounts.yield_vault;
+        vault.total_staked = vault.total_staked.checked_add(credited).ok_or(UnifiedError::Overflow)?;
+        update_position_weight(vault, position, now)?;
 
-        // Apply temporary penalty to bonding curve (1% for 24 hours)
-        curve.sabotage_penalty_bps = 100; // 1%
-        curve.sabotage_end_ts = Clock::get()?.unix_timestamp + (24 * 60 * 60); // 24 hours
+        emit!(YieldStakeChanged {
+            mint: vault.mint,
+            owner: position.owner,
+            staked: position.staked,
+            weight: position.weight,
+            lock_end: position.lock_end,
+            total_staked: vault.total_staked,
+            total_weight: vault.total_weight,
+            timestamp: now,
+        });
+        Ok(())
+    }
 
-        emit!(SabotageEvent {
-            mint: curve.mint,
-            perpetrator: ctx.accounts.user.key(),
-            penalty_bps: curve.sabotage_penalty_bps,
-            timestamp: Clock::get()?.unix_timestamp,
+    /// Withdraws staked tokens once the position's lock has ended. Rewards
+    /// earned so far stay claimable.
+    pub fn unstake_yield(ctx: Context<ModifyYieldStake>, amount: u64) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = &mut *ctx.accounts;
+        let position = &mut accounts.position;
+        require!(amount > 0 && amount <= position.staked, UnifiedError::InvalidInput);
+        require!(now >= position.lock_end, UnifiedError::StakeLocked);
+        checkpoint_position(&mut accounts.yield_vault, position)?;
+
+        position.staked -= amount;
+        let vault = &mut accounts.yield_vault;
+        vault.total_staked -= amount;
+        update_position_weight(vault, position, now)?;
+
+        let seeds = &[b"yield_vault", vault.mint.as_ref(), &[vault.bump]];
+        let signer = &[&seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.stake_vault.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.owner_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer
+            ),
+            amount,
+            accounts.mint.decimals
+        )?;
+
+        emit!(YieldStakeChanged {
+            mint: vault.mint,
+            owner: position.owner,
+            staked: position.staked,
+            weight: position.weight,
+            lock_end: position.lock_end,
+            total_staked: vault.total_staked,
+            total_weight: vault.total_weight,
+            timestamp: now,
         });
+        Ok(())
+    }
+
+    /// Drops the multiplier of a position whose lock has ended, so it stops
+    /// out-earning unlocked stakes. Permissionless.
+    pub fn expire_stake_lock(ctx: Context<ExpireStakeLock>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.yield_vault;
+        let position = &mut ctx.accounts.position;
+        require!(now >= position.lock_end, UnifiedError::StakeLocked);
+        require!(position.weight != position.staked, UnifiedError::InvalidInput);
+        checkpoint_position(vault, position)?;
+        update_position_weight(vault, position, now)?;
 
+        emit!(YieldStakeChanged {
+            mint: vault.mint,
+            owner: position.owner,
+            staked: position.staked,
+            weight: position.weight,
+            lock_end: position.lock_end,
+            total_staked: vault.total_staked,
+            total_weight: vault.total_weight,
+            timestamp: now,
+        });
         Ok(())
     }
 
-    pub fn seize_locked_tokens(ctx: Context<SeizeAssets>) -> Result<()> {
-        let config = &ctx.accounts.config;
-        
-        // SECURITY: Only admin can seize (after successful takeover vote)
-        require!(
-            ctx.accounts.authority.key() == config.admin, 
-            UnifiedError::Unauthorized
-        );
+    /// Lets the creator top up the mint's stakers with quote-asset rewards,
+    /// shared out like yield fees.
+    pub fn deposit_yield_rewards(ctx: Context<DepositYieldRewards>, amount: u64) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_STAKING)?;
+        require!(amount > 0, UnifiedError::InvalidInput);
 
-        let vesting = &mut ctx.accounts.vesting;
-        let old_owner = vesting.owner;
-        vesting.owner = ctx.accounts.new_owner.key();
-        
-        emit!(AssetsSeized {
-            mint: vesting.mint,
-            old_owner,
-            new_owner: vesting.owner,
-            amount: vesting.total_amount,
+        if ctx.accounts.curve.quote_mint == Pubkey::default() {
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.creator.key(), &ctx.accounts.yield_vault.key(), amount),
+                &[ctx.accounts.creator.to_account_info(), ctx.accounts.yield_vault.to_account_info(),
+                  ctx.accounts.system_program.to_account_info()]
+            )?;
+        } else {
+            let (Some(quote_mint), Some(from), Some(to), Some(quote_token_program)) = (
+                ctx.accounts.quote_mint.as_ref(),
+                ctx.accounts.creator_quote_account.as_ref(),
+                ctx.accounts.yield_quote_vault.as_ref(),
+                ctx.accounts.quote_token_program.as_ref(),
+            ) else {
+                return err!(UnifiedError::InvalidInput);
+            };
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    quote_token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: quote_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.creator.to_account_info(),
+                    }
+                ),
+                amount,
+                quote_mint.decimals
+            )?;
+        }
+        accrue_yield(&mut ctx.accounts.yield_vault, amount)?;
+
+        emit!(YieldRewardsDeposited {
+            mint: ctx.accounts.curve.mint,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
         });
+        Ok(())
+    }
+
+    /// Pays out a position's accrued yield, in the curve's quote asset.
+    pub fn claim_yield(ctx: Context<ClaimYield>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let vault = &mut ctx.accounts.yield_vault;
+        let position = &mut ctx.accounts.position;
+        checkpoint_position(vault, position)?;
+        update_position_weight(vault, position, Clock::get()?.unix_timestamp)?;
+
+        let amount = position.pending;
+        require!(amount > 0, UnifiedError::NothingToClaim);
+        position.pending = 0;
+        position.total_claimed = position.total_claimed.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+        vault.total_claimed = vault.total_claimed.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+
+        let seeds = &[b"yield_vault", vault.mint.as_ref(), &[vault.bump]];
+        let signer = &[&seeds[..]];
+        if ctx.accounts.curve.quote_mint == Pubkey::default() {
+            transfer_lamports(&vault.to_account_info(), &ctx.accounts.owner.to_account_info(), amount)?;
+        } else {
+            let (Some(quote_mint), Some(from), Some(to), Some(quote_token_program)) = (
+                ctx.accounts.quote_mint.as_ref(),
+                ctx.accounts.yield_quote_vault.as_ref(),
+                ctx.accounts.owner_quote_account.as_ref(),
+                ctx.accounts.quote_token_program.as_ref(),
+            ) else {
+                return err!(UnifiedError::InvalidInput);
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    quote_token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: quote_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: vault.to_account_info(),
+                    },
+                    signer
+                ),
+                amount,
+                quote_mint.decimals
+            )?;
+        }
 
+        emit!(YieldClaimed {
+            mint: vault.mint,
+            owner: position.owner,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
     // ═══════════════════════════════════════════════════════════════════════
-    // PREDICTION MARKETS (Native)
+    // REFERRALS
     // ═══════════════════════════════════════════════════════════════════════
 
-    pub fn create_market(
-        ctx: Context<CreateMarket>, 
-        id: u64, 
-        title: String, 
-        end_timestamp: i64
-    ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        require!(title.len() <= 100, UnifiedError::InvalidInput);
-        
-        market.id = id;
-        market.title = title;
-        market.end_timestamp = end_timestamp;
-        market.resolved = false;
-        market.result = None;
-        market.total_pool = 0;
-        market.yes_pool = 0;
-        market.no_pool = 0;
-        market.oracle = ctx.accounts.authority.key();
-        
-        msg!("Market created: {} - {}", id, market.title);
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_LAUNCH)?;
+        let referrer = &mut ctx.accounts.referrer_account;
+        referrer.owner = ctx.accounts.owner.key();
+        referrer.pending = 0;
+        referrer.total_earned = 0;
+        referrer.total_volume = 0;
+        referrer.trades = 0;
+        referrer.launches = 0;
+        referrer.bump = ctx.bumps.referrer_account;
         Ok(())
     }
 
-    pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: bool) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        require!(!market.resolved, UnifiedError::AlreadyResolved);
-        
-        market.resolved = true;
-        market.result = Some(outcome);
-        
-        msg!("Market resolved. Outcome: {}", outcome);
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let referrer = &mut ctx.accounts.referrer_account;
+        let amount = referrer.pending;
+        require!(amount > 0, UnifiedError::NothingToClaim);
+        referrer.pending = 0;
+
+        transfer_lamports(&referrer.to_account_info(), &ctx.accounts.owner.to_account_info(), amount)?;
+
+        emit!(ReferralFeesClaimed {
+            referrer: referrer.owner,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
         Ok(())
     }
 
-    pub fn place_bet(ctx: Context<PlaceBet>, outcome: bool, amount: u64) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        require!(!market.resolved, UnifiedError::AlreadyResolved);
-        
-        // Transfer SOL from user to the market PDA
-        // We use system_program::transfer via CpiContext for typical transfers, 
-        // but here 'invoke' is simpler given we don't need CPI signer for the user.
-        // However, Anchor 'system_program::transfer' is cleaner.
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: market.to_account_info(),
-            },
+    /// Returns the rent of a buyer's launch-window allocation once the window is over.
+    pub fn close_launch_allocation(ctx: Context<CloseLaunchAllocation>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !in_launch_window(&ctx.accounts.config, &ctx.accounts.curve, now),
+            UnifiedError::LaunchWindowActive
         );
-        anchor_lang::system_program::transfer(cpi_context, amount)?;
-        
-        // Update State
-        market.total_pool = market.total_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
-        if outcome {
-            market.yes_pool = market.yes_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
-        } else {
-            market.no_pool = market.no_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
-        }
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRICE ORACLE
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Creates the observation ring buffer of a curve. Permissionless.
+    pub fn init_price_observations(ctx: Context<InitPriceObservations>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let observations = &mut ctx.accounts.observations;
+        observations.mint = ctx.accounts.curve.mint;
+        observations.bump = ctx.bumps.observations;
+        record_observation(&mut ctx.accounts.curve, observations, Clock::get()?.unix_timestamp)
+    }
+
+    /// Checkpoints the curve's cumulative price. Permissionless; keepers call
+    /// it so TWAP windows stay covered when the curve trades rarely.
+    pub fn record_price_observation(ctx: Context<RecordPriceObservation>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        record_observation(
+            &mut ctx.accounts.curve,
+            &mut ctx.accounts.observations,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    /// Returns the curve's time-weighted average price over the last
+    /// `window_secs`, in lamports per whole token, as return data.
+    pub fn get_twap(ctx: Context<GetTwap>, window_secs: i64) -> Result<u64> {
+        twap_price(&ctx.accounts.curve, &ctx.accounts.observations, Clock::get()?.unix_timestamp, window_secs)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // LIMIT & STOP ORDERS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Escrows SOL (buy) or tokens (sell) plus a keeper tip in an order that
+    /// any keeper can fill once the curve's spot price crosses `trigger_price`
+    /// (lamports per whole token) in the `trigger` direction. One open order
+    /// per owner and mint.
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        is_buy: bool,
+        trigger: OrderTrigger,
+        trigger_price: u64,
+        amount: u64,
+        min_out: u64,
+        keeper_tip: u64
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        require!(!ctx.accounts.curve.graduated, UnifiedError::AlreadyGraduated);
+        check_sol_quoted(&ctx.accounts.curve)?;
+        require!(amount > 0 && trigger_price > 0, UnifiedError::InvalidInput);
+        require!(keeper_tip <= MAX_KEEPER_TIP, UnifiedError::InvalidInput);
+
+        let order = &mut ctx.accounts.order;
+        order.owner = ctx.accounts.owner.key();
+        order.mint = ctx.accounts.curve.mint;
+        order.is_buy = is_buy;
+        order.trigger = trigger;
+        order.trigger_price = trigger_price;
+        order.amount = amount;
+        order.min_out = min_out;
+        order.keeper_tip = keeper_tip;
+        order.created_at = Clock::get()?.unix_timestamp;
+        order.bump = ctx.bumps.order;
+
+        let lamports = if is_buy {
+            amount.checked_add(keeper_tip).ok_or(UnifiedError::Overflow)?
+        } else {
+            let mint = ctx.accounts.mint.to_account_info();
+            // Escrowed tokens must be sellable in full, so transfer-fee mints are out
+            require!(transfer_fee(&mint, amount)? == 0, UnifiedError::UnsupportedMint);
+            let order_vault = ctx.accounts.order_vault.as_ref().ok_or(UnifiedError::InvalidInput)?;
+            let owner_token_account = ctx.accounts.owner_token_account.as_ref()
+                .ok_or(UnifiedError::InvalidInput)?;
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: owner_token_account.to_account_info(),
+                        mint,
+                        to: order_vault.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    }
+                ),
+                amount,
+                ctx.accounts.mint.decimals
+            )?;
+            keeper_tip
+        };
+
+        if lamports > 0 {
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.owner.key(), &order.key(), lamports),
+                &[ctx.accounts.owner.to_account_info(), order.to_account_info(),
+                  ctx.accounts.system_program.to_account_info()],
+            )?;
+        }
+
+        emit!(OrderPlaced {
+            owner: order.owner,
+            mint: order.mint,
+            is_buy,
+            trigger,
+            trigger_price,
+            amount,
+            min_out,
+            keeper_tip,
+            timestamp: order.created_at,
+        });
+
+        Ok(())
+    }
+
+    /// Closes an open order and returns its escrow and tip to the owner.
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let order = &ctx.accounts.order;
+        if !order.is_buy {
+            let order_vault = ctx.accounts.order_vault.as_ref().ok_or(UnifiedError::InvalidInput)?;
+            let owner_token_account = ctx.accounts.owner_token_account.as_ref()
+                .ok_or(UnifiedError::InvalidInput)?;
+            release_order_vault(
+                order,
+                order_vault,
+                Some(owner_token_account.to_account_info()),
+                &ctx.accounts.mint,
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        }
+
+        emit!(OrderCancelled {
+            owner: order.owner,
+            mint: order.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: fills an order through the regular swap path once its
+    /// trigger is met and pays the keeper the order's tip. Whatever is left in
+    /// the order (refunds, rent) goes back to the owner.
+    pub fn execute_order(ctx: Context<ExecuteOrder>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let accounts = &mut *ctx.accounts;
+        let order = &accounts.order;
+        let is_buy = order.is_buy;
+        require!(!accounts.curve.graduated, UnifiedError::AlreadyGraduated);
+
+        let now = Clock::get()?.unix_timestamp;
+        // Launch-window buy limits are per wallet; keep orders out of the window
+        require!(!in_launch_window(&accounts.config, &accounts.curve, now), UnifiedError::LaunchWindowActive);
+
+        let spot_price = math::spot_price(&curve_reserves(&accounts.curve)).map_err(UnifiedError::from)?;
+        let triggered = match order.trigger {
+            OrderTrigger::AtOrBelow => spot_price <= order.trigger_price,
+            OrderTrigger::AtOrAbove => spot_price >= order.trigger_price,
+        };
+        require!(triggered, UnifiedError::OrderNotTriggered);
+
+        let (fees, fee_tier) = trader_fees(
+            &accounts.config, &accounts.mars_holder_account, &accounts.mars_stake_position, &order.owner
+        )?;
+        let quote = quote_exact_in(&fees, &accounts.curve, is_buy, order.amount, now)?;
+        let min_out = if quote.refund > 0 {
+            math::mul_div(order.min_out, quote.amount_in, order.amount).map_err(UnifiedError::from)?
+        } else {
+            order.min_out
+        };
+        let received = if is_buy {
+            quote.amount_out - transfer_fee(&accounts.mint.to_account_info(), quote.amount_out)?
+        } else {
+            quote.amount_out
+        };
+        require!(received >= min_out, UnifiedError::SlippageExceeded);
+
+        let routed = record_trade(
+            &accounts.config,
+            &mut accounts.curve,
+            &mut accounts.creator_fee_vault,
+            &mut accounts.yield_vault,
+            None,
+            accounts.order.owner,
+            is_buy,
+            &quote,
+            fee_tier,
+        )?;
+
+        // The order account trades on the owner's behalf
+        let order = &accounts.order;
+        let order_seeds = &[b"order", order.mint.as_ref(), order.owner.as_ref(), &[order.bump]];
+        let order_signer = &[&order_seeds[..]];
+        let (user_token_account, owner_token_account) = if is_buy {
+            let owner_token_account = accounts.owner_token_account.as_ref()
+                .ok_or(UnifiedError::InvalidInput)?;
+            (owner_token_account.to_account_info(), None)
+        } else {
+            let order_vault = accounts.order_vault.as_ref().ok_or(UnifiedError::InvalidInput)?;
+            (order_vault.to_account_info(), accounts.owner_token_account.as_ref().map(|a| a.to_account_info()))
+        };
+
+        TradeTransfer {
+            user: order.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            decimals: accounts.mint.decimals,
+            user_token_account,
+            curve: accounts.curve.to_account_info(),
+            curve_token_vault: accounts.curve_token_vault.to_account_info(),
+            admin_treasury: accounts.admin_treasury.to_account_info(),
+            yield_vault: accounts.yield_vault.to_account_info(),
+            creator_fee_vault: accounts.creator_fee_vault.to_account_info(),
+            referrer: None,
+            token_program: accounts.token_program.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            quote: None,
+        }.execute_signed(&accounts.curve, is_buy, &quote, &routed, order_signer)?;
+
+        if let Some(order_vault) = accounts.order_vault.as_ref() {
+            release_order_vault(
+                order,
+                order_vault,
+                owner_token_account,
+                &accounts.mint,
+                accounts.owner.to_account_info(),
+                accounts.token_program.to_account_info(),
+            )?;
+        }
+
+        transfer_lamports(&order.to_account_info(), &accounts.keeper.to_account_info(), order.keeper_tip)?;
+
+        emit!(OrderExecuted {
+            owner: order.owner,
+            mint: order.mint,
+            keeper: accounts.keeper.key(),
+            is_buy,
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            trigger_price: order.trigger_price,
+            spot_price,
+            keeper_tip: order.keeper_tip,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // AMM POOL (POST-GRADUATION)
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Moves a graduated curve's real SOL and remaining tokens into an in-program
+    /// constant-product pool. The initial LP supply is minted into a lock vault
+    /// that has no withdrawal path. Permissionless.
+    pub fn migrate_to_pool(ctx: Context<MigrateToPool>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let curve = &mut ctx.accounts.curve;
+        require!(curve.graduated, UnifiedError::NotGraduated);
+        require!(!curve.migrated, UnifiedError::AlreadyMigrated);
+        // The AMM pool pairs tokens with SOL only
+        check_sol_quoted(curve)?;
+
+        let sol_amount = curve.real_sol;
+        let vault_amount = ctx.accounts.curve_token_vault.amount;
+        // Token-2022 transfer fees are withheld from what reaches the pool
+        let token_amount = vault_amount - transfer_fee(&ctx.accounts.mint.to_account_info(), vault_amount)?;
+        let lp_locked = math::initial_lp_supply(sol_amount, token_amount)
+            .map_err(UnifiedError::from)?;
+
+        curve.migrated = true;
+        curve.real_sol = 0;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.mint = curve.mint;
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.sol_reserve = sol_amount;
+        pool.token_reserve = token_amount;
+        pool.bump = ctx.bumps.pool;
+
+        // Move reserves out of the curve
+        let curve_seeds = &[b"curve", curve.mint.as_ref(), &[curve.bump]];
+        let curve_signer = &[&curve_seeds[..]];
+
+        transfer_lamports(&curve.to_account_info(), &pool.to_account_info(), sol_amount)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.curve_token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: curve.to_account_info(),
+                },
+                curve_signer
+            ),
+            vault_amount,
+            ctx.accounts.mint.decimals
+        )?;
+
+        // Lock the initial LP supply
+        let pool_seeds = &[b"pool", pool.mint.as_ref(), &[pool.bump]];
+        let pool_signer = &[&pool_seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_lock_vault.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                pool_signer
+            ),
+            lp_locked
+        )?;
+
+        emit!(PoolCreated {
+            mint: pool.mint,
+            pool: pool.key(),
+            lp_mint: pool.lp_mint,
+            sol_amount,
+            token_amount,
+            lp_locked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn pool_swap(ctx: Context<PoolSwap>, is_buy: bool, amount: u64, min_out: u64) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let config = &ctx.accounts.config;
+        let pool = &mut ctx.accounts.pool;
+        require!(amount > 0, UnifiedError::InvalidInput);
+
+        let (fees, fee_tier) = trader_fees(
+            config, &ctx.accounts.mars_holder_account, &ctx.accounts.mars_stake_position,
+            &ctx.accounts.user.key()
+        )?;
+        let quote = if is_buy {
+            math::pool_buy_exact_in(pool.sol_reserve, pool.token_reserve, &fees, amount)
+        } else {
+            math::pool_sell_exact_in(pool.sol_reserve, pool.token_reserve, &fees, amount)
+        }.map_err(UnifiedError::from)?;
+        let mint = ctx.accounts.mint.to_account_info();
+        let received = if is_buy {
+            quote.amount_out - transfer_fee(&mint, quote.amount_out)?
+        } else {
+            quote.amount_out
+        };
+        require!(received >= min_out, UnifiedError::SlippageExceeded);
+        // On sells `amount` is what the pool receives; the trader also covers any transfer fee
+        let tokens_in = if is_buy { 0 } else { with_transfer_fee(&mint, quote.amount_in)? };
+
+        // STATE UPDATE
+        pool.sol_reserve = quote.reserves.real_sol;
+        pool.token_reserve = quote.reserves.virtual_tokens as u64;
+        ctx.accounts.creator_fee_vault.pending = ctx.accounts.creator_fee_vault.pending
+            .checked_add(quote.creator_fee).ok_or(UnifiedError::Overflow)?;
+        accrue_yield(&mut ctx.accounts.yield_vault, quote.yield_fee)?;
+
+        if fee_tier > 0 {
+            emit!(FeeTierApplied {
+                mint: pool.mint,
+                trader: ctx.accounts.user.key(),
+                fee_tier,
+                platform_fee: quote.platform_fee,
+                yield_fee: quote.yield_fee,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        // Pool trades carry no referral cut
+        emit!(TradeExecuted {
+            trader: ctx.accounts.user.key(),
+            mint: pool.mint,
+            is_buy,
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            platform_fee: quote.platform_fee,
+            yield_fee: quote.yield_fee,
+            creator_fee: quote.creator_fee,
+            penalty: quote.penalty,
+            fee_tier,
+            referrer: None,
+            referral_fee: 0,
+            virtual_sol: pool.sol_reserve as u128,
+            virtual_tokens: pool.token_reserve as u128,
+            real_sol: pool.sol_reserve,
+            pool: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        emit!(PriceUpdated {
+            mint: pool.mint,
+            sol_reserves: pool.sol_reserve,
+            token_reserves: pool.token_reserve,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        // --- EXTERNAL INTERACTIONS ---
+
+        let seeds = &[b"pool", pool.mint.as_ref(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        if is_buy {
+            if quote.platform_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(&ctx.accounts.user.key(), &config.admin, quote.platform_fee),
+                    &[ctx.accounts.user.to_account_info(), ctx.accounts.admin_treasury.to_account_info(),
+                      ctx.accounts.system_program.to_account_info()],
+                )?;
+            }
+            if quote.yield_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(&ctx.accounts.user.key(), &ctx.accounts.yield_vault.key(), quote.yield_fee),
+                    &[ctx.accounts.user.to_account_info(), ctx.accounts.yield_vault.to_account_info(),
+                      ctx.accounts.system_program.to_account_info()],
+                )?;
+            }
+            if quote.creator_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(&ctx.accounts.user.key(), &ctx.accounts.creator_fee_vault.key(), quote.creator_fee),
+                    &[ctx.accounts.user.to_account_info(), ctx.accounts.creator_fee_vault.to_account_info(),
+                      ctx.accounts.system_program.to_account_info()],
+                )?;
+            }
+
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.user.key(), &pool.key(), quote.amount_in - quote.total_fee()),
+                &[ctx.accounts.user.to_account_info(), pool.to_account_info(),
+                  ctx.accounts.system_program.to_account_info()],
+            )?;
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.pool_token_vault.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer
+                ),
+                quote.amount_out,
+                ctx.accounts.mint.decimals
+            )?;
+        } else {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.user_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.pool_token_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    }
+                ),
+                tokens_in,
+                ctx.accounts.mint.decimals
+            )?;
+
+            let pool_info = pool.to_account_info();
+            transfer_lamports(&pool_info, &ctx.accounts.admin_treasury.to_account_info(), quote.platform_fee)?;
+            transfer_lamports(&pool_info, &ctx.accounts.yield_vault.to_account_info(), quote.yield_fee)?;
+            transfer_lamports(&pool_info, &ctx.accounts.creator_fee_vault.to_account_info(), quote.creator_fee)?;
+            transfer_lamports(&pool_info, &ctx.accounts.user.to_account_info(), quote.amount_out)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<ModifyLiquidity>,
+        max_sol: u64,
+        max_tokens: u64,
+        min_lp_out: u64
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_SWAP)?;
+        let pool = &mut ctx.accounts.pool;
+        let deposit = math::add_liquidity_quote(
+            pool.sol_reserve, pool.token_reserve, ctx.accounts.lp_mint.supply, max_sol, max_tokens
+        ).map_err(UnifiedError::from)?;
+        require!(deposit.lp_amount >= min_lp_out, UnifiedError::SlippageExceeded);
+
+        pool.sol_reserve = pool.sol_reserve.checked_add(deposit.sol_amount)
+            .ok_or(UnifiedError::Overflow)?;
+        pool.token_reserve = pool.token_reserve.checked_add(deposit.token_amount)
+            .ok_or(UnifiedError::Overflow)?;
+
+        invoke(
+            &system_instruction::transfer(&ctx.accounts.user.key(), &pool.key(), deposit.sol_amount),
+            &[ctx.accounts.user.to_account_info(), pool.to_account_info(),
+              ctx.accounts.system_program.to_account_info()],
+        )?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }
+            ),
+            with_transfer_fee(&ctx.accounts.mint.to_account_info(), deposit.token_amount)?,
+            ctx.accounts.mint.decimals
+        )?;
+
+        let seeds = &[b"pool", pool.mint.as_ref(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer
+            ),
+            deposit.lp_amount
+        )?;
+
+        emit!(LiquidityChanged {
+            mint: pool.mint,
+            provider: ctx.accounts.user.key(),
+            is_deposit: true,
+            lp_amount: deposit.lp_amount,
+            sol_amount: deposit.sol_amount,
+            token_amount: deposit.token_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<ModifyLiquidity>,
+        lp_amount: u64,
+        min_sol_out: u64,
+        min_tokens_out: u64
+    ) -> Result<()> {
+        check_withdrawals_open(&ctx.accounts.config)?;
+        let pool = &mut ctx.accounts.pool;
+        let withdrawal = math::remove_liquidity_quote(
+            pool.sol_reserve, pool.token_reserve, ctx.accounts.lp_mint.supply, lp_amount
+        ).map_err(UnifiedError::from)?;
+        require!(withdrawal.sol_amount >= min_sol_out, UnifiedError::SlippageExceeded);
+        let tokens_received = withdrawal.token_amount
+            - transfer_fee(&ctx.accounts.mint.to_account_info(), withdrawal.token_amount)?;
+        require!(tokens_received >= min_tokens_out, UnifiedError::SlippageExceeded);
+
+        pool.sol_reserve = pool.sol_reserve.checked_sub(withdrawal.sol_amount)
+            .ok_or(UnifiedError::Overflow)?;
+        pool.token_reserve = pool.token_reserve.checked_sub(withdrawal.token_amount)
+            .ok_or(UnifiedError::Overflow)?;
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }
+            ),
+            lp_amount
+        )?;
+
+        let seeds = &[b"pool", pool.mint.as_ref(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer
+            ),
+            withdrawal.token_amount,
+            ctx.accounts.mint.decimals
+        )?;
+
+        transfer_lamports(&pool.to_account_info(), &ctx.accounts.user.to_account_info(), withdrawal.sol_amount)?;
+
+        emit!(LiquidityChanged {
+            mint: pool.mint,
+            provider: ctx.accounts.user.key(),
+            is_deposit: false,
+            lp_amount,
+            sol_amount: withdrawal.sol_amount,
+            token_amount: withdrawal.token_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // GAME MECHANICS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub fn initiate_takeover(ctx: Context<InitiateTakeover>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_GAME)?;
+        let curve = &mut ctx.accounts.curve;
+        
+        require!(!curve.takeover_active, UnifiedError::TakeoverInProgress);
+        require!(!curve.graduated, UnifiedError::AlreadyGraduated);
+        
+        // Must own 5% of circulating supply (800M tokens = 80% of 1B)
+        let circulating_supply = math::CURVE_SUPPLY;
+        let required_stake = circulating_supply / 20; // 5%
+
+        // Staked tokens vote too, boosted by their lock multiplier
+        let staked_power = match ctx.accounts.position.as_ref() {
+            Some(position) => stake_weight(position, Clock::get()?.unix_timestamp)?,
+            None => 0,
+        };
+        let voting_power = ctx.accounts.user_token_account.amount
+            .checked_add(staked_power)
+            .ok_or(UnifiedError::Overflow)?;
+        require!(
+            voting_power >= required_stake,
+            UnifiedError::InsufficientStakeForTakeover
+        );
+
+        // Activate takeover
+        curve.takeover_active = true;
+        curve.takeover_initiator = ctx.accounts.user.key();
+
+        emit!(TakeoverInitiated {
+            mint: curve.mint,
+            initiator: ctx.accounts.user.key(),
+            stake: voting_power,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn sabotage(ctx: Context<Sabotage>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_GAME)?;
+        let config = &ctx.accounts.config;
+        let curve = &mut ctx.accounts.curve;
+        
+        // Cost: 2 SOL, or the quote asset's configured cost
+        let cost_lamports = quote_asset(config, curve.quote_mint)
+            .ok_or(UnifiedError::UnsupportedMint)?
+            .sabotage_cost;
+        
+        // Distribution:
+        // 50% -> Curve PDA (benefits token holders)
+        // 30% -> Admin treasury
+        // 20% -> Yield distributor, or the curve's yield vault for SPL quotes
+        let to_curve = cost_lamports / 2;
+        let to_admin = (cost_lamports * 3) / 10;
+        let to_yield = cost_lamports - to_curve - to_admin;
+
+        // Transfer sabotage fee
+        if curve.quote_mint == Pubkey::default() {
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.user.key(), &curve.key(), to_curve),
+                &[ctx.accounts.user.to_account_info(), curve.to_account_info(), 
+                  ctx.accounts.system_program.to_account_info()],
+            )?;
+            
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.user.key(), &config.admin, to_admin),
+                &[ctx.accounts.user.to_account_info(), ctx.accounts.admin_treasury.to_account_info(), 
+                  ctx.accounts.system_program.to_account_info()],
+            )?;
+            
+            invoke(
+                &system_instruction::transfer(&ctx.accounts.user.key(), &config.yield_distributor, to_yield),
+                &[ctx.accounts.user.to_account_info(), ctx.accounts.yield_distributor.to_account_info(), 
+                  ctx.accounts.system_program.to_account_info()],
+            )?;
+        } else {
+            // The distributor has no way to pay out quote tokens, so their
+            // yield share goes to the stakers of this curve like a swap's does
+            let (Some(quote_mint), Some(from), Some(curve_vault), Some(admin_account), Some(yield_vault),
+                Some(yield_quote_vault), Some(quote_token_program)) = (
+                ctx.accounts.quote_mint.as_ref(),
+                ctx.accounts.user_quote_account.as_ref(),
+                ctx.accounts.curve_quote_vault.as_ref(),
+                ctx.accounts.admin_quote_account.as_ref(),
+                ctx.accounts.yield_vault.as_mut(),
+                ctx.accounts.yield_quote_vault.as_ref(),
+                ctx.accounts.quote_token_program.as_ref(),
+            ) else {
+                return err!(UnifiedError::InvalidInput);
+            };
+            require_keys_eq!(yield_quote_vault.owner, yield_vault.key(), UnifiedError::InvalidInput);
+            accrue_yield(yield_vault, to_yield)?;
+            for (to, amount) in [(curve_vault, to_curve), (admin_account, to_admin), (yield_quote_vault, to_yield)] {
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        quote_token_program.to_account_info(),
+                        TransferChecked {
+                            from: from.to_account_info(),
+                            mint: quote_mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: ctx.accounts.user.to_account_info(),
+                        }
+                    ),
+                    amount,
+                    quote_mint.decimals
+                )?;
+            }
+        }
+
+        // Apply temporary penalty to bonding curve (1% for 24 hours)
+        curve.sabotage_penalty_bps = 100; // 1%
+        curve.sabotage_end_ts = Clock::get()?.unix_timestamp + (24 * 60 * 60); // 24 hours
+
+        emit!(SabotageEvent {
+            mint: curve.mint,
+            perpetrator: ctx.accounts.user.key(),
+            penalty_bps: curve.sabotage_penalty_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn seize_locked_tokens(ctx: Context<SeizeAssets>) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_VESTING)?;
+        let config = &ctx.accounts.config;
+        
+        // SECURITY: Only admin can seize (after successful takeover vote)
+        require!(
+            ctx.accounts.authority.key() == config.admin, 
+            UnifiedError::Unauthorized
+        );
+
+        check_no_timelock(config)?;
+
+        let new_owner = ctx.accounts.new_owner.key();
+        seize_vesting(&mut ctx.accounts.vesting, &mut ctx.accounts.curve, new_owner);
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PREDICTION MARKETS (Native)
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub fn create_market(
+        ctx: Context<CreateMarket>, 
+        id: u64, 
+        title: String, 
+        end_timestamp: i64
+    ) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_MARKETS)?;
+        let market = &mut ctx.accounts.market;
+        require!(title.len() <= 100, UnifiedError::InvalidInput);
+        
+        market.id = id;
+        market.title = title;
+        market.end_timestamp = end_timestamp;
+        market.resolved = false;
+        market.result = None;
+        market.total_pool = 0;
+        market.yes_pool = 0;
+        market.no_pool = 0;
+        market.oracle = ctx.accounts.authority.key();
+        
+        msg!("Market created: {} - {}", id, market.title);
+        Ok(())
+    }
+
+    pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: bool) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_MARKETS)?;
+        let market = &mut ctx.accounts.market;
+        require!(!market.resolved, UnifiedError::AlreadyResolved);
+        
+        market.resolved = true;
+        market.result = Some(outcome);
+        
+        msg!("Market resolved. Outcome: {}", outcome);
+        Ok(())
+    }
+
+    /// Admin override of a market's outcome, for an oracle that resolved it
+    /// wrongly or never will. Open markets are resolved, and markets that are
+    /// already resolved have their result replaced; `MarketOverridden` records
+    /// the previous result. Once a timelock delay is set this goes through
+    /// `queue_action`; with a multisig admin, through a proposal.
+    pub fn override_market(ctx: Context<OverrideMarket>, outcome: bool) -> Result<()> {
+        check_no_timelock(&ctx.accounts.config)?;
+        apply_market_override(&mut ctx.accounts.market, outcome)
+    }
+
+    pub fn place_bet(ctx: Context<PlaceBet>, outcome: bool, amount: u64) -> Result<()> {
+        check_not_paused(&ctx.accounts.config, PAUSE_MARKETS)?;
+        let market = &mut ctx.accounts.market;
+        require!(!market.resolved, UnifiedError::AlreadyResolved);
+        
+        // Transfer SOL from user to the market PDA
+        // We use system_program::transfer via CpiContext for typical transfers, 
+        // but here 'invoke' is simpler given we don't need CPI signer for the user.
+        // However, Anchor 'system_program::transfer' is cleaner.
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: market.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+        
+        // Update State
+        market.total_pool = market.total_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+        if outcome {
+            market.yes_pool = market.yes_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+        } else {
+            market.no_pool = market.no_pool.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+        }
+
+        msg!("Bet placed on {}: {} lamports", outcome, amount);
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// PRICE ORACLE
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Rolls the curve's cumulative price forward to `now` at the current spot price.
+fn accumulate_price(curve: &mut BondingCurve, now: i64) -> Result<()> {
+    let elapsed = now.saturating_sub(curve.last_price_update);
+    if elapsed > 0 {
+        let price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+        curve.price_cumulative = math::accumulate_price(curve.price_cumulative, price, elapsed);
+        curve.last_price_update = now;
+    }
+    Ok(())
+}
+
+/// Writes a checkpoint into the ring buffer, at most one per
+/// `MIN_OBSERVATION_INTERVAL` so a burst of trades cannot flush history.
+fn record_observation(curve: &mut BondingCurve, observations: &mut PriceObservations, now: i64) -> Result<()> {
+    accumulate_price(curve, now)?;
+    if observations.count > 0 {
+        let latest = observations.observations[observations.head as usize];
+        if now - latest.timestamp < MIN_OBSERVATION_INTERVAL {
+            return Ok(());
+        }
+        observations.head = (observations.head + 1) % MAX_OBSERVATIONS as u16;
+    }
+    observations.observations[observations.head as usize] = Observation {
+        timestamp: now,
+        price_cumulative: curve.price_cumulative,
+    };
+    observations.count = observations.count.saturating_add(1).min(MAX_OBSERVATIONS as u16);
+    Ok(())
+}
+
+/// Time-weighted average price over at least the last `window_secs`, measured
+/// from the newest checkpoint that is old enough. Fails if the buffer does not
+/// reach back that far.
+pub fn twap_price(curve: &BondingCurve, observations: &PriceObservations, now: i64, window_secs: i64) -> Result<u64> {
+    require!(window_secs > 0, UnifiedError::InvalidInput);
+    let target = now.checked_sub(window_secs).ok_or(UnifiedError::Overflow)?;
+
+    let start = (0..observations.count as usize)
+        .map(|age| {
+            let index = (observations.head as usize + MAX_OBSERVATIONS - age) % MAX_OBSERVATIONS;
+            observations.observations[index]
+        })
+        .find(|observation| observation.timestamp <= target)
+        .ok_or(UnifiedError::InsufficientPriceHistory)?;
+
+    let price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+    let elapsed = now.saturating_sub(curve.last_price_update);
+    let cumulative = math::accumulate_price(curve.price_cumulative, price, elapsed);
+    Ok(math::twap(start.price_cumulative, start.timestamp, cumulative, now).map_err(UnifiedError::from)?)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// CIRCUIT BREAKER
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Whether a halt is still in its cool-off at `now`.
+fn is_halted(curve: &BondingCurve, now: i64) -> bool {
+    curve.trading_halted && now < curve.halted_until
+}
+
+/// Rejects trades while the curve is halted; a halt lifts itself once its
+/// cool-off has passed.
+fn check_not_halted(curve: &mut BondingCurve, now: i64) -> Result<()> {
+    if curve.trading_halted {
+        require!(!is_halted(curve, now), UnifiedError::TradingHalted);
+        curve.trading_halted = false;
+        curve.halt_reason = HaltReason::None;
+        curve.halted_until = 0;
+        // Measure the next window from the price trading resumes at
+        curve.breaker_window_start = now;
+        curve.breaker_reference_price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+    }
+    Ok(())
+}
+
+/// Starts a new breaker window from the pre-trade spot price once the current
+/// one has elapsed, so a trade is never measured against a stale reference.
+/// Runs before the trade is applied to the reserves.
+fn roll_breaker_window(config: &GlobalConfig, curve: &mut BondingCurve, now: i64) -> Result<()> {
+    if config.circuit_breaker_bps == 0 {
+        return Ok(());
+    }
+    if now - curve.breaker_window_start >= config.circuit_breaker_window_secs {
+        curve.breaker_window_start = now;
+        curve.breaker_reference_price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+    }
+    Ok(())
+}
+
+/// Halts the curve if its spot price has moved more than the configured
+/// threshold since the start of the current window. The trade that trips the
+/// breaker stands; the halt applies to the ones after it.
+fn trip_circuit_breaker(config: &GlobalConfig, curve: &mut BondingCurve, now: i64) -> Result<()> {
+    if config.circuit_breaker_bps == 0 {
+        return Ok(());
+    }
+
+    let price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+    let move_bps = math::price_change_bps(curve.breaker_reference_price, price).map_err(UnifiedError::from)?;
+    if move_bps > config.circuit_breaker_bps as u64 {
+        curve.trading_halted = true;
+        curve.halt_reason = HaltReason::PriceMove;
+        curve.halted_until = now.saturating_add(config.circuit_breaker_cooloff_secs);
+        emit!(TradingHalted {
+            mint: curve.mint,
+            reason: HaltReason::PriceMove,
+            reference_price: curve.breaker_reference_price,
+            price,
+            move_bps,
+            halted_until: curve.halted_until,
+            timestamp: now,
+        });
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// LAUNCH
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Sets up the curve, the creator's vesting schedule and the creator fee and
+/// yield vaults of a new launch. Bumps are left to the caller.
+#[allow(clippy::too_many_arguments)]
+fn init_launch_state(
+    curve: &mut BondingCurve,
+    vesting: &mut VestingAccount,
+    creator_fee_vault: &mut CreatorFeeVault,
+    yield_vault: &mut YieldVault,
+    creator: Pubkey,
+    mint: Pubkey,
+    sector: Sector,
+    quote: &QuoteAsset,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    curve.creator = creator;
+    curve.mint = mint;
+    curve.sector = sector;
+    curve.virtual_sol = quote.initial_virtual_quote as u128;
+    curve.virtual_tokens = math::INITIAL_VIRTUAL_TOKENS;
+    curve.real_sol = 0;
+    curve.graduated = false;
+    curve.takeover_active = false;
+    curve.takeover_initiator = Pubkey::default();
+    curve.sabotage_penalty_bps = 0;
+    curve.sabotage_end_ts = 0;
+    curve.migrated = false;
+    curve.created_at = now;
+    curve.price_cumulative = 0;
+    curve.last_price_update = now;
+    curve.breaker_window_start = now;
+    curve.breaker_reference_price = math::spot_price(&curve_reserves(curve)).map_err(UnifiedError::from)?;
+    curve.trading_halted = false;
+    curve.halt_reason = HaltReason::None;
+    curve.halted_until = 0;
+    curve.quote_mint = quote.mint;
+    curve.graduation_threshold = quote.graduation_threshold;
+
+    vesting.owner = creator;
+    vesting.mint = mint;
+    vesting.total_amount = math::CREATOR_ALLOCATION;
+    vesting.released_amount = 0;
+    vesting.start_ts = now;
+    vesting.end_ts = now
+        .checked_add(365 * 24 * 60 * 60)
+        .ok_or(UnifiedError::Overflow)?;
+
+    creator_fee_vault.mint = mint;
+    creator_fee_vault.pending = 0;
+    creator_fee_vault.total_claimed = 0;
+
+    yield_vault.mint = mint;
+    yield_vault.total_staked = 0;
+    yield_vault.total_weight = 0;
+    yield_vault.reward_index = 0;
+    yield_vault.undistributed = 0;
+    yield_vault.total_accrued = 0;
+    yield_vault.total_claimed = 0;
+    yield_vault.stream_updated_at = now;
+
+    Ok(())
+}
+
+/// Mints 80% of the supply to the curve (trading) and 20% to the vesting vault.
+fn mint_launch_supply<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    curve_token_vault: AccountInfo<'info>,
+    vesting_token_vault: AccountInfo<'info>,
+    curve: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    for (to, amount) in [(curve_token_vault, math::CURVE_SUPPLY), (vesting_token_vault, math::CREATOR_ALLOCATION)] {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint.clone(),
+                    to,
+                    authority: curve.clone(),
+                },
+                signer
+            ),
+            amount
+        )?;
+    }
+    Ok(())
+}
+
+/// Credits the launch to `referrer`, if any, and returns its owner.
+fn attribute_launch(referrer: Option<&mut Account<ReferrerAccount>>, creator: Pubkey) -> Result<Option<Pubkey>> {
+    let Some(referrer) = referrer else {
+        return Ok(None);
+    };
+    require!(referrer.owner != creator, UnifiedError::SelfReferral);
+    referrer.launches = referrer.launches.checked_add(1).ok_or(UnifiedError::Overflow)?;
+    Ok(Some(referrer.owner))
+}
+
+/// Metaplex metadata of a business token: no royalties, creators or collection.
+fn business_metadata(name: String, symbol: String, uri: String) -> DataV2 {
+    DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    }
+}
+
+/// Rewrites the metadata a Token-2022 business keeps in its mint, signed by
+/// the curve as update authority. The creator tops the mint up first when the
+/// new fields make it grow past what its lamports cover.
+fn update_token_2022_metadata<'info>(
+    accounts: &UpdateBusinessMetadata<'info>,
+    curve_signer: &[&[&[u8]]],
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Result<()> {
+    let mint = accounts.mint.to_account_info();
+    let new_len = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        let current = state.get_variable_len_extension::<TokenMetadata>()?;
+        let updated = TokenMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            ..current.clone()
+        };
+        (mint.data_len() + updated.tlv_size_of()?)
+            .checked_sub(current.tlv_size_of()?)
+            .ok_or(UnifiedError::Overflow)?
+    };
+    let shortfall = Rent::get()?.minimum_balance(new_len).saturating_sub(mint.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: accounts.creator.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    for (field, value) in [(Field::Name, name), (Field::Symbol, symbol), (Field::Uri, uri)] {
+        token_interface::token_metadata_update_field(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    program_id: accounts.token_program.to_account_info(),
+                    metadata: mint.clone(),
+                    update_authority: accounts.curve.to_account_info(),
+                },
+                curve_signer
+            ),
+            field,
+            value.to_string()
+        )?;
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// CURVE MATH
+// ═══════════════════════════════════════════════════════════════════════
+
+fn check_fee_bounds(platform_fee_bps: u16, yield_fee_bps: u16, creator_fee_bps: u16) -> Result<()> {
+    let total = platform_fee_bps as u32 + yield_fee_bps as u32 + creator_fee_bps as u32;
+    require!(total <= MAX_TRADING_FEE_BPS as u32, UnifiedError::FeeTooHigh);
+    Ok(())
+}
+
+/// Direct admin setters are only available until a timelock delay is set.
+fn check_no_timelock(config: &GlobalConfig) -> Result<()> {
+    require!(config.admin_delay_secs == 0, UnifiedError::TimelockRequired);
+    Ok(())
+}
+
+/// Validates and applies a config change, whether it arrives directly or
+/// through the timelock queue.
+fn apply_admin_action(config: &mut GlobalConfig, action: &AdminActionKind) -> Result<()> {
+    match action {
+        AdminActionKind::UpdateConfig { platform_fee_bps, yield_fee_bps, yield_distributor } => {
+            check_fee_bounds(*platform_fee_bps, *yield_fee_bps, config.creator_fee_bps)?;
+            require!(*yield_distributor != Pubkey::default(), UnifiedError::InvalidInput);
+
+            config.platform_fee_bps = *platform_fee_bps;
+            config.yield_fee_bps = *yield_fee_bps;
+            config.yield_distributor = *yield_distributor;
+
+            emit!(ConfigUpdated {
+                platform_fee_bps: *platform_fee_bps,
+                yield_fee_bps: *yield_fee_bps,
+                yield_distributor: *yield_distributor,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        AdminActionKind::ProposeAdmin { new_admin } => {
+            config.pending_admin = *new_admin;
+
+            emit!(AdminProposed {
+                admin: config.admin,
+                pending_admin: *new_admin,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        AdminActionKind::SetCircuitBreaker { threshold_bps, window_secs, cooloff_secs } => {
+            require!(*window_secs >= 0 && *cooloff_secs >= 0, UnifiedError::InvalidInput);
+            require!(*threshold_bps == 0 || *window_secs > 0, UnifiedError::InvalidInput);
+
+            config.circuit_breaker_bps = *threshold_bps;
+            config.circuit_breaker_window_secs = *window_secs;
+            config.circuit_breaker_cooloff_secs = *cooloff_secs;
+        }
+        AdminActionKind::SetLaunchProtection { window_secs, max_buy_per_tx, max_buy_per_wallet, launch_fee_bps } => {
+            require!(*window_secs >= 0, UnifiedError::InvalidInput);
+            require!(*launch_fee_bps <= 5_000, UnifiedError::FeeTooHigh);
+
+            config.launch_window_secs = *window_secs;
+            config.launch_max_buy_per_tx = *max_buy_per_tx;
+            config.launch_max_buy_per_wallet = *max_buy_per_wallet;
+            config.launch_fee_bps = *launch_fee_bps;
+        }
+        AdminActionKind::SetReferralShare { referral_share_bps } => {
+            require!(*referral_share_bps <= 10_000, UnifiedError::FeeTooHigh);
+            config.referral_share_bps = *referral_share_bps;
+        }
+        AdminActionKind::SetCreatorFee { creator_fee_bps } => {
+            require!(*creator_fee_bps <= MAX_CREATOR_FEE_BPS, UnifiedError::FeeTooHigh);
+            check_fee_bounds(config.platform_fee_bps, config.yield_fee_bps, *creator_fee_bps)?;
+            config.creator_fee_bps = *creator_fee_bps;
+        }
+        AdminActionKind::SetFeeTiers { mars_mint, tiers } => {
+            require!(tiers.len() <= MAX_FEE_TIERS, UnifiedError::InvalidInput);
+            for (i, tier) in tiers.iter().enumerate() {
+                require!(tier.discount_bps <= 10_000, UnifiedError::InvalidInput);
+                if i > 0 {
+                    require!(tier.min_balance > tiers[i - 1].min_balance, UnifiedError::InvalidInput);
+                }
+            }
+
+            config.mars_mint = *mars_mint;
+            config.fee_tier_count = tiers.len() as u8;
+            config.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+            config.fee_tiers[..tiers.len()].copy_from_slice(tiers);
+        }
+        AdminActionKind::SetAdminDelay { delay_secs } => {
+            require!((0..=MAX_ADMIN_DELAY_SECS).contains(delay_secs), UnifiedError::InvalidInput);
+            config.admin_delay_secs = *delay_secs;
+        }
+        AdminActionKind::SetQuoteAsset { asset } => {
+            require!(asset.mint != Pubkey::default(), UnifiedError::InvalidInput);
+            require!(asset.initial_virtual_quote > 0 && asset.graduation_threshold > 0, UnifiedError::InvalidInput);
+
+            let count = config.quote_asset_count as usize;
+            match config.quote_assets[..count].iter().position(|a| a.mint == asset.mint) {
+                Some(i) => config.quote_assets[i] = *asset,
+                None => {
+                    require!(count < MAX_QUOTE_ASSETS, UnifiedError::InvalidInput);
+                    config.quote_assets[count] = *asset;
+                    config.quote_asset_count += 1;
+                }
+            }
+        }
+        AdminActionKind::RemoveQuoteAsset { mint } => {
+            let count = config.quote_asset_count as usize;
+            let i = config.quote_assets[..count]
+                .iter()
+                .position(|a| a.mint == *mint)
+                .ok_or(UnifiedError::InvalidInput)?;
+            config.quote_assets.copy_within(i + 1..count, i);
+            config.quote_assets[count - 1] = QuoteAsset::default();
+            config.quote_asset_count -= 1;
+        }
+        // Need accounts beyond the config; only `execute_action` applies them
+        AdminActionKind::SeizeLockedTokens { .. } | AdminActionKind::OverrideMarket { .. } => {
+            return err!(UnifiedError::InvalidInput)
+        }
+    }
+    Ok(())
+}
+
+fn check_multisig_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(signers.len() <= MAX_MULTISIG_SIGNERS, UnifiedError::InvalidInput);
+    require!(threshold > 0 && threshold as usize <= signers.len(), UnifiedError::InvalidInput);
+    for (i, signer) in signers.iter().enumerate() {
+        require!(!signers[..i].contains(signer), UnifiedError::InvalidInput);
+    }
+    Ok(())
+}
+
+fn multisig_signer_index(multisig: &Multisig, key: &Pubkey) -> Result<usize> {
+    multisig
+        .signers
+        .iter()
+        .position(|s| s == key)
+        .ok_or_else(|| error!(UnifiedError::NotMultisigSigner))
+}
+
+/// Curve parameters for `quote_mint`: the program-wide SOL settings for
+/// `Pubkey::default()`, otherwise its allowlist entry.
+fn quote_asset(config: &GlobalConfig, quote_mint: Pubkey) -> Option<QuoteAsset> {
+    if quote_mint == Pubkey::default() {
+        return Some(QuoteAsset {
+            mint: Pubkey::default(),
+            initial_virtual_quote: math::INITIAL_VIRTUAL_SOL as u64,
+            graduation_threshold: math::GRADUATION_THRESHOLD,
+            launch_max_buy_per_tx: config.launch_max_buy_per_tx,
+            launch_max_buy_per_wallet: config.launch_max_buy_per_wallet,
+            sabotage_cost: SABOTAGE_COST_LAMPORTS,
+        });
+    }
+    config.quote_assets[..config.quote_asset_count as usize]
+        .iter()
+        .find(|a| a.mint == quote_mint)
+        .copied()
+}
+
+/// Quote asset a new curve launches with: native SOL unless an allowlisted
+/// SPL mint is passed. Mints with a transfer fee are rejected.
+fn launch_quote_asset(config: &GlobalConfig, quote_mint: Option<&InterfaceAccount<Mint>>) -> Result<QuoteAsset> {
+    let Some(quote_mint) = quote_mint else {
+        return Ok(quote_asset(config, Pubkey::default()).unwrap());
+    };
+    let info = quote_mint.to_account_info();
+    require!(
+        *info.owner != token_2022::ID
+            || token_interface::get_mint_extension_data::<TransferFeeConfig>(&info).is_err(),
+        UnifiedError::UnsupportedMint
+    );
+    Ok(quote_asset(config, quote_mint.key()).ok_or(UnifiedError::UnsupportedMint)?)
+}
+
+fn check_sol_quoted(curve: &BondingCurve) -> Result<()> {
+    require!(curve.quote_mint == Pubkey::default(), UnifiedError::UnsupportedMint);
+    Ok(())
+}
+
+/// Quote reserve at which buys are capped and the curve graduates. The AMM
+/// pool pairs tokens with SOL only, so SPL-quoted curves never graduate and
+/// keep trading on the curve instead of stranding their reserves.
+fn graduation_cap(curve: &BondingCurve) -> u64 {
+    if curve.quote_mint == Pubkey::default() {
+        curve.graduation_threshold
+    } else {
+        u64::MAX
+    }
+}
+
+/// Hands a creator's locked allocation, and with it the creator fee stream,
+/// to a new owner.
+fn seize_vesting(vesting: &mut VestingAccount, curve: &mut BondingCurve, new_owner: Pubkey) {
+    let old_owner = vesting.owner;
+    vesting.owner = new_owner;
+    curve.creator = new_owner;
+
+    emit!(AssetsSeized {
+        mint: vesting.mint,
+        old_owner,
+        new_owner,
+        amount: vesting.total_amount,
+    });
+}
+
+/// Sets a market's outcome on the admin's authority, resolved or not.
+fn apply_market_override(market: &mut Account<Market>, outcome: bool) -> Result<()> {
+    let previous_result = market.result;
+    market.resolved = true;
+    market.result = Some(outcome);
+
+    emit!(MarketOverridden {
+        market: market.key(),
+        id: market.id,
+        previous_result,
+        outcome,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+fn check_not_paused(config: &GlobalConfig, family: u8) -> Result<()> {
+    require!(config.paused & family == 0, UnifiedError::Paused);
+    Ok(())
+}
+
+fn check_withdrawals_open(config: &GlobalConfig) -> Result<()> {
+    require!(!config.withdrawals_paused, UnifiedError::Paused);
+    Ok(())
+}
+
+/// Fee rates currently configured for trading.
+fn fee_schedule(config: &GlobalConfig) -> FeeSchedule {
+    FeeSchedule {
+        platform_fee_bps: config.platform_fee_bps,
+        yield_fee_bps: config.yield_fee_bps,
+        creator_fee_bps: config.creator_fee_bps,
+    }
+}
+
+/// Fee rates for `trader`, discounted by the tier their held plus staked $MARS
+/// reaches. Returns the schedule and the 1-based tier (0 = no discount).
+fn trader_fees(
+    config: &GlobalConfig,
+    mars_holder_account: &Option<InterfaceAccount<TokenAccount>>,
+    mars_stake_position: &Option<Account<YieldPosition>>,
+    trader: &Pubkey,
+) -> Result<(FeeSchedule, u8)> {
+    let fees = fee_schedule(config);
+    if mars_holder_account.is_none() && mars_stake_position.is_none() {
+        return Ok((fees, 0));
+    }
+    require!(config.mars_mint != Pubkey::default(), UnifiedError::InvalidFeeTierAccount);
+
+    let mut balance: u64 = 0;
+    if let Some(holder) = mars_holder_account {
+        require!(
+            holder.mint == config.mars_mint && holder.owner == *trader,
+            UnifiedError::InvalidFeeTierAccount
+        );
+        balance = holder.amount;
+    }
+    if let Some(position) = mars_stake_position {
+        require!(
+            position.mint == config.mars_mint && position.owner == *trader,
+            UnifiedError::InvalidFeeTierAccount
+        );
+        balance = balance.saturating_add(position.staked);
+    }
+
+    let tiers = &config.fee_tiers[..config.fee_tier_count as usize];
+    match tiers.iter().rposition(|tier| balance >= tier.min_balance) {
+        Some(index) => {
+            let discounted = fees.with_discount(tiers[index].discount_bps)
+                .map_err(UnifiedError::from)?;
+            Ok((discounted, index as u8 + 1))
+        }
+        None => Ok((fees, 0)),
+    }
+}
+
+fn in_launch_window(config: &GlobalConfig, curve: &BondingCurve, now: i64) -> bool {
+    now < curve.created_at.saturating_add(config.launch_window_secs)
+}
+
+/// Adds the decaying anti-sniper fee to the yield leg of buys made during
+/// the launch window.
+fn with_launch_fee(
+    mut fees: FeeSchedule,
+    config: &GlobalConfig,
+    curve: &BondingCurve,
+    is_buy: bool,
+    now: i64,
+) -> FeeSchedule {
+    if is_buy {
+        let elapsed = now.saturating_sub(curve.created_at);
+        let extra = math::launch_fee_bps(config.launch_fee_bps, elapsed, config.launch_window_secs);
+        fees.yield_fee_bps = fees.yield_fee_bps.saturating_add(extra);
+    }
+    fees
+}
+
+fn curve_reserves(curve: &BondingCurve) -> CurveReserves {
+    CurveReserves {
+        virtual_sol: curve.virtual_sol,
+        virtual_tokens: curve.virtual_tokens,
+        real_sol: curve.real_sol,
+    }
+}
+
+/// Sabotage penalty in effect on `curve` at `now`.
+fn active_penalty_bps(curve: &BondingCurve, now: i64) -> u16 {
+    if curve.sabotage_end_ts > now {
+        curve.sabotage_penalty_bps
+    } else {
+        0
+    }
+}
+
+/// Prices a trade with a fixed input amount against the on-chain curve.
+pub fn quote_exact_in(
+    fees: &FeeSchedule,
+    curve: &BondingCurve,
+    is_buy: bool,
+    amount_in: u64,
+    now: i64,
+) -> Result<SwapQuote> {
+    let reserves = curve_reserves(curve);
+    let penalty_bps = active_penalty_bps(curve, now);
+    let quote = if is_buy {
+        math::buy_exact_in_with_threshold(&reserves, fees, penalty_bps, amount_in, graduation_cap(curve))
+    } else {
+        math::sell_exact_in(&reserves, fees, penalty_bps, amount_in)
+    };
+    Ok(quote.map_err(UnifiedError::from)?)
+}
+
+/// Prices a trade that must deliver exactly `amount_out` against the on-chain curve.
+pub fn quote_exact_out(
+    fees: &FeeSchedule,
+    curve: &BondingCurve,
+    is_buy: bool,
+    amount_out: u64,
+    now: i64,
+) -> Result<SwapQuote> {
+    let reserves = curve_reserves(curve);
+    let penalty_bps = active_penalty_bps(curve, now);
+    let quote = if is_buy {
+        math::buy_exact_out_with_threshold(&reserves, fees, penalty_bps, amount_out, graduation_cap(curve))
+    } else {
+        math::sell_exact_out(&reserves, fees, penalty_bps, amount_out)
+    };
+    Ok(quote.map_err(UnifiedError::from)?)
+}
+
+fn check_deadline(deadline_ts: Option<i64>, now: i64) -> Result<()> {
+    if let Some(deadline) = deadline_ts {
+        require!(now <= deadline, UnifiedError::DeadlineExceeded);
+    }
+    Ok(())
+}
+
+/// Rejects a trade that moves the spot price by more than `max_price_impact_bps`.
+fn check_price_impact(curve: &BondingCurve, quote: &SwapQuote, max_price_impact_bps: Option<u16>) -> Result<()> {
+    if let Some(max_bps) = max_price_impact_bps {
+        let impact = math::price_impact_bps(&curve_reserves(curve), &quote.reserves)
+            .map_err(UnifiedError::from)?;
+        require!(impact <= max_bps as u64, UnifiedError::PriceImpactExceeded);
+    }
+    Ok(())
+}
+
+/// Enforces the launch window's per-transaction and per-wallet buy limits,
+/// tracking the buyer's running total in their launch allocation. Limits are
+/// in units of the curve's quote asset.
+fn check_launch_limits(
+    config: &GlobalConfig,
+    curve: &BondingCurve,
+    allocation: Option<&mut Account<LaunchAllocation>>,
+    allocation_bump: Option<u8>,
+    buyer: Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    if !in_launch_window(config, curve, Clock::get()?.unix_timestamp) {
+        return Ok(());
+    }
+    // A delisted quote asset keeps trading without launch limits
+    let (max_per_tx, max_per_wallet) = quote_asset(config, curve.quote_mint)
+        .map_or((0, 0), |a| (a.launch_max_buy_per_tx, a.launch_max_buy_per_wallet));
+    if max_per_tx > 0 {
+        require!(lamports <= max_per_tx, UnifiedError::LaunchBuyLimitExceeded);
+    }
+    let allocation = allocation.ok_or(UnifiedError::LaunchAllocationRequired)?;
+    if allocation.buyer == Pubkey::default() {
+        allocation.mint = curve.mint;
+        allocation.buyer = buyer;
+        allocation.bump = allocation_bump.ok_or(UnifiedError::LaunchAllocationRequired)?;
+    }
+    allocation.bought = allocation.bought.checked_add(lamports).ok_or(UnifiedError::Overflow)?;
+    if max_per_wallet > 0 {
+        require!(
+            allocation.bought <= max_per_wallet,
+            UnifiedError::LaunchBuyLimitExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Applies a priced trade to the curve and moves the funds.
+fn settle_swap(accounts: &mut Swap, bumps: &SwapBumps, is_buy: bool, quote: &SwapQuote, fee_tier: u8) -> Result<()> {
+    let quote_transfer = swap_quote_transfer(accounts)?;
+    // Referral balances are kept in lamports
+    require!(
+        quote_transfer.is_none() || accounts.referrer_account.is_none(),
+        UnifiedError::UnsupportedMint
+    );
+    let config = &accounts.config;
+
+    if is_buy {
+        check_launch_limits(
+            config,
+            &accounts.curve,
+            accounts.launch_allocation.as_mut(),
+            bumps.launch_allocation,
+            accounts.user.key(),
+            quote.amount_in,
+        )?;
+    }
+
+    let routed = record_trade(
+        config,
+        &mut accounts.curve,
+        &mut accounts.creator_fee_vault,
+        &mut accounts.yield_vault,
+        accounts.referrer_account.as_mut(),
+        accounts.user.key(),
+        is_buy,
+        quote,
+        fee_tier,
+    )?;
+    if let Some(observations) = accounts.price_observations.as_mut() {
+        record_observation(&mut accounts.curve, observations, Clock::get()?.unix_timestamp)?;
+    }
+
+    TradeTransfer {
+        user: accounts.user.to_account_info(),
+        mint: accounts.mint.to_account_info(),
+        decimals: accounts.mint.decimals,
+        user_token_account: accounts.user_token_account.to_account_info(),
+        curve: accounts.curve.to_account_info(),
+        curve_token_vault: accounts.curve_token_vault.to_account_info(),
+        admin_treasury: accounts.admin_treasury.to_account_info(),
+        yield_vault: accounts.yield_vault.to_account_info(),
+        creator_fee_vault: accounts.creator_fee_vault.to_account_info(),
+        referrer: accounts.referrer_account.as_ref().map(|r| r.to_account_info()),
+        token_program: accounts.token_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        quote: quote_transfer,
+    }.execute(&accounts.curve, is_buy, quote, &routed)
+}
+
+/// Collects the quote-token accounts of a swap on an SPL-quoted curve; their
+/// mints and owners are checked by the `Swap` constraints.
+fn swap_quote_transfer<'info>(accounts: &Swap<'info>) -> Result<Option<QuoteTransfer<'info>>> {
+    if accounts.curve.quote_mint == Pubkey::default() {
+        return Ok(None);
+    }
+    let (Some(mint), Some(user_account), Some(curve_vault), Some(admin_account), Some(yield_account),
+        Some(creator_fee_vault), Some(token_program)) = (
+        accounts.quote_mint.as_ref(),
+        accounts.user_quote_account.as_ref(),
+        accounts.curve_quote_vault.as_ref(),
+        accounts.admin_quote_account.as_ref(),
+        accounts.yield_quote_account.as_ref(),
+        accounts.creator_fee_quote_vault.as_ref(),
+        accounts.quote_token_program.as_ref(),
+    ) else {
+        return err!(UnifiedError::InvalidInput);
+    };
+    Ok(Some(QuoteTransfer {
+        mint: mint.to_account_info(),
+        decimals: mint.decimals,
+        user_account: user_account.to_account_info(),
+        curve_vault: curve_vault.to_account_info(),
+        admin_account: admin_account.to_account_info(),
+        yield_account: yield_account.to_account_info(),
+        creator_fee_vault: creator_fee_vault.to_account_info(),
+        token_program: token_program.to_account_info(),
+    }))
+}
+
+/// Where the platform fee of a trade ends up once the referrer's cut is taken.
+struct RoutedFees {
+    platform_fee: u64,
+    referral_fee: u64,
+}
+
+/// Adds a yield fee to the vault's index. With nothing staked it is held in
+/// `undistributed` and streamed out later (see `stream_undistributed`).
+fn accrue_yield(vault: &mut YieldVault, amount: u64) -> Result<()> {
+    vault.total_accrued = vault.total_accrued.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+    stream_undistributed(vault, Clock::get()?.unix_timestamp)?;
+    if amount == 0 {
+        return Ok(());
+    }
+    if vault.total_weight == 0 {
+        vault.undistributed = vault.undistributed.checked_add(amount).ok_or(UnifiedError::Overflow)?;
+        return Ok(());
+    }
+    vault.reward_index = math::reward_index_after(vault.reward_index, amount, vault.total_weight)
+        .map_err(UnifiedError::from)?;
+    Ok(())
+}
+
+/// Releases the part of `undistributed` that streamed out since the last
+/// update into the index. The stream only runs while something is staked and
+/// is rolled before every weight change, so a stake placed just before a fee
+/// earns only its share of the time it was staked, not the whole pot.
+fn stream_undistributed(vault: &mut YieldVault, now: i64) -> Result<()> {
+    let elapsed = now.saturating_sub(vault.stream_updated_at);
+    vault.stream_updated_at = now;
+    if vault.total_weight == 0 || vault.undistributed == 0 {
+        return Ok(());
+    }
+    let released = math::streamed_reward(vault.undistributed, elapsed, math::YIELD_STREAM_SECS)
+        .map_err(UnifiedError::from)?;
+    if released == 0 {
+        return Ok(());
+    }
+    vault.reward_index = math::reward_index_after(vault.reward_index, released, vault.total_weight)
+        .map_err(UnifiedError::from)?;
+    vault.undistributed = vault.undistributed.checked_sub(released).ok_or(UnifiedError::Overflow)?;
+    Ok(())
+}
+
+/// Moves what `position` earned since its last checkpoint into `pending`,
+/// after rolling the vault's stream forward. Must run before its stake changes.
+fn checkpoint_position(vault: &mut YieldVault, position: &mut YieldPosition) -> Result<()> {
+    stream_undistributed(vault, Clock::get()?.unix_timestamp)?;
+    let earned = math::rewards_earned(position.weight, vault.reward_index, position.reward_index_paid)
+        .map_err(UnifiedError::from)?;
+    position.pending = position.pending.checked_add(earned).ok_or(UnifiedError::Overflow)?;
+    position.reward_index_paid = vault.reward_index;
+    Ok(())
+}
+
+/// Stake counted with its lock multiplier, or at face value once the lock ran out.
+fn stake_weight(position: &YieldPosition, now: i64) -> Result<u64> {
+    if now >= position.lock_end {
+        return Ok(position.staked);
+    }
+    let weight = math::bps_of(position.staked as u128, position.multiplier_bps).map_err(UnifiedError::from)?;
+    u64::try_from(weight).map_err(|_| error!(UnifiedError::Overflow))
+}
+
+/// Re-derives the position's weight after a stake or lock change, dropping a
+/// lapsed lock's multiplier. Checkpoint the position first.
+fn update_position_weight(vault: &mut YieldVault, position: &mut YieldPosition, now: i64) -> Result<()> {
+    if now >= position.lock_end {
+        position.multiplier_bps = STAKE_LOCK_TIERS[0].1;
+        position.lock_tier = 0;
+    }
+    let weight = stake_weight(position, now)?;
+    vault.total_weight = vault.total_weight
+        .checked_sub(position.weight)
+        .ok_or(UnifiedError::Overflow)?
+        .checked_add(weight)
+        .ok_or(UnifiedError::Overflow)?;
+    position.weight = weight;
+    Ok(())
+}
+
+/// Books a priced trade: moves the curve reserves, accrues creator, yield and
+/// referral fees, and emits the trade events (including graduation).
+#[allow(clippy::too_many_arguments)]
+fn record_trade(
+    config: &GlobalConfig,
+    curve: &mut BondingCurve,
+    creator_fee_vault: &mut CreatorFeeVault,
+    yield_vault: &mut YieldVault,
+    referrer: Option<&mut Account<ReferrerAccount>>,
+    trader: Pubkey,
+    is_buy: bool,
+    quote: &SwapQuote,
+    fee_tier: u8,
+) -> Result<RoutedFees> {
+    let now = Clock::get()?.unix_timestamp;
+    check_not_halted(curve, now)?;
+    roll_breaker_window(config, curve, now)?;
+
+    // STATE UPDATE
+    accumulate_price(curve, now)?;
+    curve.virtual_sol = quote.reserves.virtual_sol;
+    curve.virtual_tokens = quote.reserves.virtual_tokens;
+    curve.real_sol = quote.reserves.real_sol;
+    trip_circuit_breaker(config, curve, now)?;
+    creator_fee_vault.pending = creator_fee_vault.pending
+        .checked_add(quote.creator_fee).ok_or(UnifiedError::Overflow)?;
+    accrue_yield(yield_vault, quote.yield_fee)?;
+
+    // Referrers take their share out of the platform fee
+    let mut routed = RoutedFees { platform_fee: quote.platform_fee, referral_fee: 0 };
+    let referrer_key = referrer.as_ref().map(|referrer| referrer.owner);
+    if let Some(referrer) = referrer {
+        require!(referrer.owner != trader, UnifiedError::SelfReferral);
+        routed.referral_fee = math::bps_of(quote.platform_fee as u128, config.referral_share_bps)
+            .map_err(UnifiedError::from)? as u64;
+        routed.platform_fee -= routed.referral_fee;
+
+        // Volume is measured in SOL on both sides of the trade
+        let volume = if is_buy { quote.amount_in } else { quote.amount_out + quote.total_fee() };
+        referrer.pending = referrer.pending.checked_add(routed.referral_fee).ok_or(UnifiedError::Overflow)?;
+        referrer.total_earned = referrer.total_earned.checked_add(routed.referral_fee).ok_or(UnifiedError::Overflow)?;
+        referrer.total_volume = referrer.total_volume.checked_add(volume).ok_or(UnifiedError::Overflow)?;
+        referrer.trades = referrer.trades.checked_add(1).ok_or(UnifiedError::Overflow)?;
+
+        emit!(ReferralFeeAccrued {
+            referrer: referrer.owner,
+            mint: curve.mint,
+            trader,
+            volume,
+            referral_fee: routed.referral_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    if fee_tier > 0 {
+        emit!(FeeTierApplied {
+            mint: curve.mint,
+            trader,
+            fee_tier,
+            platform_fee: quote.platform_fee,
+            yield_fee: quote.yield_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    // Graduation Check
+    if math::is_graduated(curve.real_sol, graduation_cap(curve)) && !curve.graduated {
+        curve.graduated = true;
+        emit!(GraduationReady {
+            mint: curve.mint,
+            sol_amount: curve.real_sol,
+            token_amount: curve.virtual_tokens as u64,
+            virtual_sol: curve.virtual_sol,
+            virtual_tokens: curve.virtual_tokens,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    emit!(TradeExecuted {
+        trader,
+        mint: curve.mint,
+        is_buy,
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        platform_fee: quote.platform_fee,
+        yield_fee: quote.yield_fee,
+        creator_fee: quote.creator_fee,
+        penalty: quote.penalty,
+        fee_tier,
+        referrer: referrer_key,
+        referral_fee: routed.referral_fee,
+        virtual_sol: curve.virtual_sol,
+        virtual_tokens: curve.virtual_tokens,
+        real_sol: curve.real_sol,
+        pool: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    // Kept for existing consumers; prefer TradeExecuted
+    emit!(PriceUpdated {
+        mint: curve.mint,
+        sol_reserves: curve.virtual_sol as u64,
+        token_reserves: curve.virtual_tokens as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(routed)
+}
+
+/// Returns whatever is left in a sell order's token vault to `owner_token_account`
+/// and closes the vault, refunding its rent to the owner.
+fn release_order_vault<'info>(
+    order: &Account<'info, Order>,
+    order_vault: &InterfaceAccount<'info, TokenAccount>,
+    owner_token_account: Option<AccountInfo<'info>>,
+    mint: &InterfaceAccount<'info, Mint>,
+    owner: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let seeds = &[b"order", order.mint.as_ref(), order.owner.as_ref(), &[order.bump]];
+    let signer = &[&seeds[..]];
+
+    let mut order_vault = order_vault.clone();
+    order_vault.reload()?;
+    if order_vault.amount > 0 {
+        let to = owner_token_account.ok_or(UnifiedError::InvalidInput)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: order_vault.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to,
+                    authority: order.to_account_info(),
+                },
+                signer
+            ),
+            order_vault.amount,
+            mint.decimals
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: order_vault.to_account_info(),
+            destination: owner,
+            authority: order.to_account_info(),
+        },
+        signer
+    ))
+}
+
+/// Reallocs a program account written by an older, shorter layout to `space`
+/// bytes, topping its rent up from `payer`. The new bytes read as zero.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(&payer.key(), &account.key(), shortfall),
+            &[payer.to_account_info(), account.clone(), system_program.to_account_info()]
+        )?;
+    }
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Moves lamports out of an account owned by this program. The System Program
+/// refuses to debit accounts that carry data, so PDAs are debited directly.
+/// The payer has to stay rent-exempt.
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+    from.sub_lamports(lamports)?;
+    to.add_lamports(lamports)?;
+    require!(
+        Rent::get()?.is_exempt(from.lamports(), from.data_len()),
+        UnifiedError::NotRentExempt
+    );
+    Ok(())
+}
+
+/// Token-2022 transfer fee withheld when `amount` of `mint` is sent this epoch.
+/// Zero for classic SPL mints and Token-2022 mints without the extension.
+fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID {
+        return Ok(0);
+    }
+    let Ok(fee_config) = token_interface::get_mint_extension_data::<TransferFeeConfig>(mint) else {
+        return Ok(0);
+    };
+    Ok(fee_config.calculate_epoch_fee(Clock::get()?.epoch, amount).ok_or(UnifiedError::Overflow)?)
+}
+
+/// Amount to send so that `amount` of `mint` arrives after the transfer fee.
+fn with_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID || amount == 0 {
+        return Ok(amount);
+    }
+    let Ok(fee_config) = token_interface::get_mint_extension_data::<TransferFeeConfig>(mint) else {
+        return Ok(amount);
+    };
+    let fee = fee_config.calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(UnifiedError::Overflow)?;
+    Ok(amount.checked_add(fee).ok_or(UnifiedError::Overflow)?)
+}
+
+/// Accounts that move funds for a curve trade.
+struct TradeTransfer<'info> {
+    user: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    user_token_account: AccountInfo<'info>,
+    curve: AccountInfo<'info>,
+    curve_token_vault: AccountInfo<'info>,
+    admin_treasury: AccountInfo<'info>,
+    yield_vault: AccountInfo<'info>,
+    creator_fee_vault: AccountInfo<'info>,
+    referrer: Option<AccountInfo<'info>>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    // Set for SPL-quoted curves; fees and reserves then move in the quote token
+    quote: Option<QuoteTransfer<'info>>,
+}
+
+/// Quote-token accounts of a trade on an SPL-quoted curve.
+struct QuoteTransfer<'info> {
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    user_account: AccountInfo<'info>,
+    curve_vault: AccountInfo<'info>,
+    admin_account: AccountInfo<'info>,
+    yield_account: AccountInfo<'info>,
+    creator_fee_vault: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+impl<'info> TradeTransfer<'info> {
+    fn execute(&self, curve: &BondingCurve, is_buy: bool, quote: &SwapQuote, routed: &RoutedFees) -> Result<()> {
+        self.execute_signed(curve, is_buy, quote, routed, &[])
+    }
+
+    /// As `execute`, for a program-derived `user` that signs with `user_signer`.
+    fn execute_signed(
+        &self,
+        curve: &BondingCurve,
+        is_buy: bool,
+        quote: &SwapQuote,
+        routed: &RoutedFees,
+        user_signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let seeds = &[b"curve", curve.mint.as_ref(), &[curve.bump]];
+        let signer = &[&seeds[..]];
+
+        // Buys pay out of the user's wallet, sells out of the curve
+        let (payer, payer_signer) = if is_buy { (&self.user, user_signer) } else { (&self.curve, &signer[..]) };
+        // `to` receives lamports; on SPL-quoted curves `quote_to` receives the quote token instead
+        let pay = |to: &AccountInfo<'info>, quote_to: Option<&AccountInfo<'info>>, lamports: u64| -> Result<()> {
+            if lamports == 0 {
+                return Ok(());
+            }
+            let Some(quote) = &self.quote else {
+                // Program accounts (the curve, an order) carry data and are debited directly
+                if *payer.owner == crate::ID {
+                    return transfer_lamports(payer, to, lamports);
+                }
+                let ix = system_instruction::transfer(payer.key, to.key, lamports);
+                let infos = [payer.clone(), to.clone(), self.system_program.clone()];
+                invoke_signed(&ix, &infos, payer_signer)?;
+                return Ok(());
+            };
+            let quote_to = quote_to.ok_or(UnifiedError::UnsupportedMint)?;
+            let from = if is_buy { &quote.user_account } else { &quote.curve_vault };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    quote.token_program.clone(),
+                    TransferChecked {
+                        from: from.clone(),
+                        mint: quote.mint.clone(),
+                        to: quote_to.clone(),
+                        authority: payer.clone(),
+                    },
+                    payer_signer
+                ),
+                lamports,
+                quote.decimals
+            )
+        };
+        let spl = self.quote.as_ref();
+
+        if !is_buy {
+            // Transfer tokens to curve, grossed up so the curve receives the quoted amount
+            let tokens_in = with_transfer_fee(&self.mint, quote.amount_in)?;
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    TransferChecked {
+                        from: self.user_token_account.clone(),
+                        mint: self.mint.clone(),
+                        to: self.curve_token_vault.clone(),
+                        authority: self.user.clone(),
+                    },
+                    user_signer
+                ),
+                tokens_in,
+                self.decimals
+            )?;
+        }
+
+        // Pay fees
+        pay(&self.admin_treasury, spl.map(|q| &q.admin_account), routed.platform_fee)?;
+        if let Some(referrer) = &self.referrer {
+            pay(referrer, None, routed.referral_fee)?;
+        }
+        pay(&self.yield_vault, spl.map(|q| &q.yield_account), quote.yield_fee)?;
+        pay(&self.creator_fee_vault, spl.map(|q| &q.creator_fee_vault), quote.creator_fee)?;
+
+        if is_buy {
+            // Transfer SOL to curve
+            pay(&self.curve, spl.map(|q| &q.curve_vault), quote.amount_in - quote.total_fee())?;
+
+            // Transfer tokens to user
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    TransferChecked {
+                        from: self.curve_token_vault.clone(),
+                        mint: self.mint.clone(),
+                        to: self.user_token_account.clone(),
+                        authority: self.curve.clone(),
+                    },
+                    signer
+                ),
+                quote.amount_out,
+                self.decimals
+            )?;
+        } else {
+            // Pay out SOL
+            pay(&self.user, spl.map(|q| &q.user_account), quote.amount_out)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = CONFIG_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: Validated in logic
+    pub yield_distributor: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump, has_one = admin @ UnifiedError::Unauthorized)]
+    pub config: Account<'info, GlobalConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: An older layout does not deserialize; the handler checks the
+    /// discriminator and the stored admin before growing it
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCurve<'info> {
+    /// CHECK: An older layout does not deserialize; the discriminator is
+    /// checked when the grown account is read back
+    #[account(mut, seeds = [b"curve", mint.key().as_ref()], bump, owner = crate::ID)]
+    pub curve: UncheckedAccount<'info>,
+
+    /// CHECK: Only its key seeds the curve and the fee vault
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 8 + 8 + 1,
+        seeds = [b"creator_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        constraint = config.pending_admin == pending_admin.key() @ UnifiedError::Unauthorized
+    )]
+    pub config: Account<'info, GlobalConfig>,
+    pub pending_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    #[account(mut, seeds = [b"config"], bump, has_one = admin @ UnifiedError::Unauthorized)]
+    pub config: Account<'info, GlobalConfig>,
+
+    // Sized for the largest variant, `SetFeeTiers` with a full table
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 8 + 1 + 32 + 4 + (8 + 2) * MAX_FEE_TIERS + 8 + 8 + 1,
+        seeds = [b"admin_action", config.action_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub action: Account<'info, AdminAction>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    #[account(seeds = [b"config"], bump, has_one = admin @ UnifiedError::Unauthorized)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"admin_action", action.id.to_le_bytes().as_ref()],
+        bump = action.bump
+    )]
+    pub action: Account<'info, AdminAction>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(mut, seeds = [b"config"], bump, has_one = admin @ UnifiedError::Unauthorized)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"admin_action", action.id.to_le_bytes().as_ref()],
+        bump = action.bump
+    )]
+    pub action: Account<'info, AdminAction>,
+
+    /// Target of a `SeizeLockedTokens` action.
+    #[account(mut)]
+    pub vesting: Option<Account<'info, VestingAccount>>,
+
+    #[account(mut)]
+    pub curve: Option<Account<'info, BondingCurve>>,
+
+    /// Target of an `OverrideMarket` action.
+    #[account(mut)]
+    pub market: Option<Account<'info, Market>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 4 + 32 * MAX_MULTISIG_SIGNERS + 1 + 8 + 4 + 1 + 1,
+        seeds = [b"multisig", creator.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Signs proposals for the multisig; holds no data
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump)]
+    pub multisig_signer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct CreateProposal<'info> {
+    #[account(mut, seeds = [b"multisig", multisig.creator.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + 32 + 8 + 32 + 32 + 4 + (32 + 1 + 1) * accounts.len() + 4 + data.len()
+            + 4 + MAX_MULTISIG_SIGNERS + 4 + 1,
+        seeds = [b"proposal", multisig.key().as_ref(), multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(seeds = [b"multisig", multisig.creator.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"proposal", multisig.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    // Read-only so a `set_multisig_signers` proposal is not overwritten on exit
+    #[account(seeds = [b"multisig", multisig.creator.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: PDA that signs the proposed instruction
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump = multisig.signer_bump)]
+    pub multisig_signer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = proposer,
+        has_one = multisig,
+        has_one = proposer,
+        seeds = [b"proposal", multisig.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: Receives the proposal rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisigSigners<'info> {
+    #[account(mut, seeds = [b"multisig", multisig.creator.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [b"multisig_signer", multisig.key().as_ref()], bump = multisig.signer_bump)]
+    pub multisig_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: String)]
+pub struct LaunchBusiness<'info> {
+    #[account(mut)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = 6,
+        mint::authority = curve,
+        seeds = [b"mint", creator.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = CURVE_SPACE,
+        seeds = [b"curve", mint.key().as_ref()],
+        bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + 8 + 1,
+        seeds = [b"creator_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + 8 + 16 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"yield_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = curve,
+        seeds = [b"vesting_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_account.owner.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// CHECK: Created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: AccountInfo<'info>,
+
+    /// Receives the creator's pre-buy
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Required for a pre-buy during the launch window
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"launch_alloc", mint.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub launch_allocation: Option<Account<'info, LaunchAllocation>>,
+
+    /// Allowlisted SPL quote mint; the curve is quoted in SOL when omitted
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated against config.admin
+    #[account(mut, address = config.admin)]
+    pub admin_treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String)]
+pub struct LaunchBusiness2022<'info> {
+    pub config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Created with its extensions in the handler
+    #[account(
+        mut,
+        seeds = [b"mint", creator.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub mint: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = CURVE_SPACE,
+        seeds = [b"curve", mint.key().as_ref()],
+        bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + 8 + 1,
+        seeds = [b"creator_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + 8 + 16 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"yield_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// CHECK: The curve's associated token account, created in the handler
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address_with_program_id(
+            &curve.key(), &mint.key(), &token_program.key()
+        )
+    )]
+    pub curve_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Created and initialized in the handler
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_token_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_account.owner.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// Allowlisted SPL quote mint; the curve is quoted in SOL when omitted
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBusinessMetadata<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump,
+        has_one = creator @ UnifiedError::Unauthorized
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(mut, address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Metaplex metadata of a classic SPL mint; omitted for Token-2022 mints,
+    /// whose metadata lives in the mint itself.
+    /// CHECK: Validated by seeds; owned by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata::ID.as_ref(), curve.mint.as_ref()],
+        bump,
+        seeds::program = metadata::ID
+    )]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// Pays for the mint to grow when Token-2022 metadata gets longer
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    #[account(address = *mint.to_account_info().owner)]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub config: Account<'info, GlobalConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == mint.key(),
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Validated against config.admin
+    #[account(mut, address = config.admin)]
+    pub admin_treasury: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", curve.mint.as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    /// Trader's $MARS token account, for fee-tier discounts
+    pub mars_holder_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Trader's staked $MARS, counted towards the fee tier
+    pub mars_stake_position: Option<Account<'info, YieldPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_account.owner.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// Required for buys during the launch window
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"launch_alloc", curve.mint.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub launch_allocation: Option<Account<'info, LaunchAllocation>>,
+
+    /// Checkpointed after the trade when provided
+    #[account(
+        mut,
+        seeds = [b"observations", curve.mint.as_ref()],
+        bump = price_observations.bump
+    )]
+    pub price_observations: Option<Account<'info, PriceObservations>>,
+
+    /// Quote-token accounts, required when the curve is quoted in an SPL mint
+    #[account(address = curve.quote_mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = user)]
+    pub user_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = curve)]
+    pub curve_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = config.admin)]
+    pub admin_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = yield_vault)]
+    pub yield_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = creator_fee_vault)]
+    pub creator_fee_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SwapBusinessToBusiness<'info> {
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Curve being sold into
+    #[account(
+        mut,
+        seeds = [b"curve", curve_in.mint.as_ref()],
+        bump = curve_in.bump
+    )]
+    pub curve_in: Account<'info, BondingCurve>,
+
+    #[account(address = curve_in.mint)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account_in.mint == mint_in.key(),
+        constraint = user_token_account_in.owner == user.key()
+    )]
+    pub user_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = curve_in,
+        associated_token::token_program = token_program_in,
+    )]
+    pub curve_token_vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", curve_in.mint.as_ref()],
+        bump = creator_fee_vault_in.bump
+    )]
+    pub creator_fee_vault_in: Account<'info, CreatorFeeVault>,
+
+    // Curve being bought from
+    #[account(
+        mut,
+        seeds = [b"curve", curve_out.mint.as_ref()],
+        bump = curve_out.bump
+    )]
+    pub curve_out: Account<'info, BondingCurve>,
+
+    #[account(address = curve_out.mint)]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account_out.mint == mint_out.key(),
+        constraint = user_token_account_out.owner == user.key()
+    )]
+    pub user_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = curve_out,
+        associated_token::token_program = token_program_out,
+    )]
+    pub curve_token_vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", curve_out.mint.as_ref()],
+        bump = creator_fee_vault_out.bump
+    )]
+    pub creator_fee_vault_out: Account<'info, CreatorFeeVault>,
+
+    /// CHECK: Validated against config.admin
+    #[account(mut, address = config.admin)]
+    pub admin_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve_in.mint.as_ref()],
+        bump = yield_vault_in.bump
+    )]
+    pub yield_vault_in: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve_out.mint.as_ref()],
+        bump = yield_vault_out.bump
+    )]
+    pub yield_vault_out: Account<'info, YieldVault>,
+
+    /// Trader's $MARS token account, for fee-tier discounts
+    pub mars_holder_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Trader's staked $MARS, counted towards the fee tier
+    pub mars_stake_position: Option<Account<'info, YieldPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_account.owner.as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Option<Account<'info, ReferrerAccount>>,
+
+    /// Required when the bought curve is in its launch window
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"launch_alloc", curve_out.mint.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub launch_allocation: Option<Account<'info, LaunchAllocation>>,
+
+    pub token_program_in: Interface<'info, TokenInterface>,
+    pub token_program_out: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Only used to match `mars_holder_account` and `mars_stake_position` ownership
+    pub trader: AccountInfo<'info>,
+
+    /// Trader's $MARS token account, for fee-tier discounts
+    pub mars_holder_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Trader's staked $MARS, counted towards the fee tier
+    pub mars_stake_position: Option<Account<'info, YieldPosition>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", vesting.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+    
+    #[account(mut)]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(address = vesting.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub vesting_token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitPriceObservations<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 2 + 2 + (8 + 16) * MAX_OBSERVATIONS + 1,
+        seeds = [b"observations", curve.mint.as_ref()],
+        bump
+    )]
+    pub observations: Account<'info, PriceObservations>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordPriceObservation<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"observations", curve.mint.as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Account<'info, PriceObservations>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [b"observations", curve.mint.as_ref()],
+        bump = observations.bump
+    )]
+    pub observations: Account<'info, PriceObservations>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"order", curve.mint.as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+
+    /// Escrow for sell orders
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = order,
+        token::token_program = token_program,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump
+    )]
+    pub order_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == curve.mint,
+        constraint = owner_token_account.owner == owner.key()
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", order.mint.as_ref(), owner.key().as_ref()],
+        bump = order.bump,
+        has_one = owner @ UnifiedError::Unauthorized
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(address = order.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump
+    )]
+    pub order_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == order.mint,
+        constraint = owner_token_account.owner == owner.key()
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", curve.mint.as_ref(), owner.key().as_ref()],
+        bump = order.bump,
+        has_one = owner @ UnifiedError::Unauthorized
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        mut,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump
+    )]
+    pub order_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The order's owner, validated by `has_one`
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// Receives bought tokens, and any unsold escrow of a sell order
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == curve.mint,
+        constraint = owner_token_account.owner == owner.key()
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated against config.admin
+    #[account(mut, address = config.admin)]
+    pub admin_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", curve.mint.as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    /// Owner's $MARS token account, for fee-tier discounts
+    pub mars_holder_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's staked $MARS, counted towards the fee tier
+    pub mars_stake_position: Option<Account<'info, YieldPosition>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateToPool<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(address = curve.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = curve,
+        associated_token::token_program = token_program,
+    )]
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 8 + 1,
+        seeds = [b"pool", mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 6,
+        mint::authority = pool,
+        seeds = [b"lp_mint", mint.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lp_mint,
+        token::authority = pool,
+        seeds = [b"lp_lock", mint.key().as_ref()],
+        bump
+    )]
+    pub lp_lock_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct PoolSwap<'info> {
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    #[account(address = pool.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-        msg!("Bet placed on {}: {} lamports", outcome, amount);
-        Ok(())
-    }
-}
+    #[account(
+        mut,
+        associated_token::mint = pool.mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
 
-// ═══════════════════════════════════════════════════════════════════════
-// CONTEXTS
-// ═══════════════════════════════════════════════════════════════════════
+    /// CHECK: Validated against config.admin
+    #[account(mut, address = config.admin)]
+    pub admin_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_vault", pool.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_vault", pool.mint.as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    /// Trader's $MARS token account, for fee-tier discounts
+    pub mars_holder_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Trader's staked $MARS, counted towards the fee tier
+    pub mars_stake_position: Option<Account<'info, YieldPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
 
 #[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(init, payer = admin, space = 8 + 32 + 2 + 2 + 32, seeds = [b"config"], bump)]
+pub struct ModifyLiquidity<'info> {
+    #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", pool.mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    #[account(address = pool.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = pool.lp_mint)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool.mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(mut)]
-    pub admin: Signer<'info>,
-    /// CHECK: Validated in logic
-    pub yield_distributor: AccountInfo<'info>,
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == pool.lp_mint,
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(name: String, symbol: String, uri: String)]
-pub struct LaunchBusiness<'info> {
-    #[account(mut)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, GlobalConfig>,
-    
+
     #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = curve,
-        seeds = [b"mint", creator.key().as_ref(), symbol.as_bytes()],
-        bump
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump,
+        has_one = creator @ UnifiedError::Unauthorized
     )]
-    pub mint: Account<'info, Mint>,
+    pub curve: Account<'info, BondingCurve>,
 
     #[account(
-        init,
-        payer = creator,
-        space = 8 + 32 + 32 + 1 + 16 + 16 + 8 + 1 + 1 + 32 + 2 + 8 + 8,
-        seeds = [b"curve", mint.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"creator_vault", curve.mint.as_ref()],
+        bump = creator_fee_vault.bump
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    /// Quote-token accounts, required when the curve is quoted in an SPL mint
+    #[account(address = curve.quote_mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = creator_fee_vault)]
+    pub creator_fee_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint)]
+    pub creator_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitYieldVault<'info> {
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
     )]
     pub curve: Account<'info, BondingCurve>,
 
     #[account(
         init,
-        payer = creator,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
-        seeds = [b"vesting", mint.key().as_ref()],
+        payer = payer,
+        space = 8 + 32 + 8 + 8 + 16 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"yield_vault", curve.mint.as_ref()],
         bump
     )]
-    pub vesting: Account<'info, VestingAccount>,
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyYieldStake<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
 
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = curve,
+        mut,
+        seeds = [b"yield_vault", mint.key().as_ref()],
+        bump = yield_vault.bump
     )]
-    pub curve_token_vault: Account<'info, TokenAccount>,
+    pub yield_vault: Account<'info, YieldVault>,
 
     #[account(
-        init,
-        payer = creator,
-        token::mint = mint,
-        token::authority = curve,
-        seeds = [b"vesting_vault", mint.key().as_ref()],
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 1 + 16 + 8 + 8 + 1,
+        seeds = [b"yield_position", mint.key().as_ref(), owner.key().as_ref()],
         bump
     )]
-    pub vesting_token_vault: Account<'info, TokenAccount>,
+    pub position: Account<'info, YieldPosition>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = owner)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = yield_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct Swap<'info> {
-    #[account(mut)]
+pub struct ClaimYield<'info> {
+    #[account(seeds = [b"config"], bump)]
     pub config: Account<'info, GlobalConfig>,
-    
+
+    #[account(seeds = [b"curve", curve.mint.as_ref()], bump = curve.bump)]
+    pub curve: Account<'info, BondingCurve>,
+
     #[account(
         mut,
-        seeds = [b"curve", curve.mint.as_ref()],
-        bump = curve.bump
+        seeds = [b"yield_vault", curve.mint.as_ref()],
+        bump = yield_vault.bump
     )]
-    pub curve: Account<'info, BondingCurve>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_position", curve.mint.as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner @ UnifiedError::Unauthorized
+    )]
+    pub position: Account<'info, YieldPosition>,
+
+    /// Quote-token accounts, required when the curve is quoted in an SPL mint
+    #[account(address = curve.quote_mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = yield_vault)]
+    pub yield_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint)]
+    pub owner_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(mut)]
-    pub curve_token_vault: Account<'info, TokenAccount>,
-    
-    /// CHECK: Validated against config.admin
-    #[account(mut, address = config.admin)]
-    pub admin_treasury: AccountInfo<'info>,
-    
-    /// CHECK: Validated against config.yield_distributor
-    #[account(mut, address = config.yield_distributor)]
-    pub yield_distributor: AccountInfo<'info>,
-    
-    pub token_program: Program<'info, Token>,
+    pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimVested<'info> {
+pub struct ExpireStakeLock<'info> {
     #[account(
         mut,
-        seeds = [b"curve", vesting.mint.as_ref()],
-        bump = curve.bump
+        seeds = [b"yield_vault", position.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    #[account(
+        mut,
+        seeds = [b"yield_position", position.mint.as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, YieldPosition>,
+}
+
+#[derive(Accounts)]
+pub struct DepositYieldRewards<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump,
+        has_one = creator @ UnifiedError::Unauthorized
     )]
     pub curve: Account<'info, BondingCurve>,
-    
+
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Account<'info, YieldVault>,
+
+    /// Quote-token accounts, required when the curve is quoted in an SPL mint
+    #[account(address = curve.quote_mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = creator)]
+    pub creator_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = yield_vault)]
+    pub yield_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(mut)]
-    pub vesting: Account<'info, VestingAccount>,
-    
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"referrer", owner.key().as_ref()],
+        bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
     #[account(mut)]
-    pub vesting_token_vault: Account<'info, TokenAccount>,
-    
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", owner.key().as_ref()],
+        bump = referrer_account.bump,
+        has_one = owner @ UnifiedError::Unauthorized
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
     #[account(mut)]
     pub owner: Signer<'info>,
-    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLaunchAllocation<'info> {
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [b"curve", curve.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"launch_alloc", curve.mint.as_ref(), buyer.key().as_ref()],
+        bump = launch_allocation.bump,
+        has_one = buyer @ UnifiedError::Unauthorized
+    )]
+    pub launch_allocation: Account<'info, LaunchAllocation>,
+
     #[account(mut)]
-    pub owner_token_account: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
+    pub buyer: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct InitiateTakeover<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
     #[account(
         mut,
         seeds = [b"curve", curve.mint.as_ref()],
@@ -705,7 +4977,14 @@ pub struct InitiateTakeover<'info> {
         constraint = user_token_account.mint == curve.mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's yield stake, counted towards their voting power
+    #[account(
+        seeds = [b"yield_position", curve.mint.as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, YieldPosition>>,
 }
 
 #[derive(Accounts)]
@@ -730,6 +5009,33 @@ pub struct Sabotage<'info> {
     /// CHECK: Validated against config.yield_distributor
     #[account(mut, address = config.yield_distributor)]
     pub yield_distributor: AccountInfo<'info>,
+
+    /// Quote-token accounts, required when the curve is quoted in an SPL mint
+    #[account(address = curve.quote_mint)]
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = user)]
+    pub user_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = curve)]
+    pub curve_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = curve.quote_mint, token::authority = config.admin)]
+    pub admin_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collects the yield share of an SPL-quoted sabotage
+    #[account(
+        mut,
+        seeds = [b"yield_vault", curve.mint.as_ref()],
+        bump = yield_vault.bump
+    )]
+    pub yield_vault: Option<Account<'info, YieldVault>>,
+
+    /// Owned by `yield_vault`; checked in the handler
+    #[account(mut, token::mint = curve.quote_mint)]
+    pub yield_quote_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
 }
@@ -741,6 +5047,13 @@ pub struct SeizeAssets<'info> {
     
     #[account(mut)]
     pub vesting: Account<'info, VestingAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve", vesting.mint.as_ref()],
+        bump = curve.bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
     
     /// CHECK: The new owner (takeover winner)
     pub new_owner: AccountInfo<'info>,
@@ -754,6 +5067,9 @@ pub struct SeizeAssets<'info> {
 #[derive(Accounts)]
 #[instruction(id: u64)]
 pub struct CreateMarket<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
     #[account(
         init, 
         payer = authority, 
@@ -768,14 +5084,30 @@ pub struct CreateMarket<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ResolveMarket<'info> {
-    #[account(mut, has_one = oracle)]
+pub struct ResolveMarket<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut, has_one = oracle)]
+    pub market: Account<'info, Market>,
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OverrideMarket<'info> {
+    #[account(seeds = [b"config"], bump, has_one = admin @ UnifiedError::Unauthorized)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut, seeds = [b"market", market.id.to_le_bytes().as_ref()], bump)]
     pub market: Account<'info, Market>,
-    pub oracle: Signer<'info>,
+    pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
+    #[account(seeds = [b"config"], bump)]
+    pub config: Account<'info, GlobalConfig>,
+
     #[account(mut)]
     pub market: Account<'info, Market>,
     #[account(mut)]
@@ -788,12 +5120,107 @@ pub struct PlaceBet<'info> {
 // STATE STRUCTURES
 // ═══════════════════════════════════════════════════════════════════════
 
+pub const MAX_FEE_TIERS: usize = 4;
+
+// Pausable instruction families (`GlobalConfig::paused` bits)
+pub const PAUSE_LAUNCH: u8 = 1 << 0;
+pub const PAUSE_SWAP: u8 = 1 << 1;
+pub const PAUSE_VESTING: u8 = 1 << 2;
+pub const PAUSE_GAME: u8 = 1 << 3;
+pub const PAUSE_MARKETS: u8 = 1 << 4;
+pub const PAUSE_STAKING: u8 = 1 << 5;
+pub const PAUSE_ALL: u8 = PAUSE_LAUNCH | PAUSE_SWAP | PAUSE_VESTING | PAUSE_GAME | PAUSE_MARKETS | PAUSE_STAKING;
+
+/// Longest metadata URI accepted by the token metadata program.
+pub const MAX_URI_LENGTH: usize = 200;
+/// Upper bound on the Token-2022 transfer fee of a launch (5%).
+pub const MAX_TRANSFER_FEE_BPS: u16 = 500;
+/// Checkpoints kept per curve for TWAP reads.
+pub const MAX_OBSERVATIONS: usize = 32;
+/// Minimum spacing between two checkpoints.
+pub const MIN_OBSERVATION_INTERVAL: i64 = 60;
+/// Upper bound on the tip an order pays its keeper (0.01 SOL).
+pub const MAX_KEEPER_TIP: u64 = 10_000_000;
+/// Upper bound on platform, yield and creator fees combined (10%).
+pub const MAX_TRADING_FEE_BPS: u16 = 1_000;
+/// Upper bound on the creator's share of each trade (1%).
+pub const MAX_CREATOR_FEE_BPS: u16 = 100;
+/// Largest signer set of a multisig; bounds the account and the approvals vector.
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+/// SPL quote mints the config can allowlist alongside native SOL.
+pub const MAX_QUOTE_ASSETS: usize = 4;
+/// Cost of sabotaging a SOL-quoted curve.
+pub const SABOTAGE_COST_LAMPORTS: u64 = 2_000_000_000;
+/// Stake lock durations and the reward multiplier each earns, in bps.
+pub const STAKE_LOCK_TIERS: [(i64, u16); 4] = [
+    (0, 10_000),
+    (30 * 86_400, 12_500),
+    (90 * 86_400, 15_000),
+    (365 * 86_400, 20_000),
+];
+/// Longest admin timelock, so a mistaken delay cannot lock governance out.
+pub const MAX_ADMIN_DELAY_SECS: i64 = 30 * 86_400;
+/// Current size of `GlobalConfig`. Fields are only ever appended, so a shorter
+/// account was written by an older version; `migrate_config` grows it.
+pub const CONFIG_SPACE: usize = 8 + 32 + 2 + 2 + 32 + 32 + 1 + (8 + 2) * MAX_FEE_TIERS + 2 + 2 + 8 + 8 + 8 + 2 + 2 + 8 + 8
+    + 1 + 1 + 32 + 8 + 8 + 1 + (32 + 8 * 5) * MAX_QUOTE_ASSETS;
+/// Current size of `BondingCurve`; older curves are grown by `migrate_curve`.
+pub const CURVE_SPACE: usize = 8 + 32 + 32 + 1 + 16 + 16 + 8 + 1 + 1 + 32 + 2 + 8 + 8 + 1 + 8 + 16 + 8 + 8 + 8 + 1 + 1 + 8
+    + 32 + 8;
+
 #[account]
 pub struct GlobalConfig {
     pub admin: Pubkey,
     pub platform_fee_bps: u16,
     pub yield_fee_bps: u16,
     pub yield_distributor: Pubkey,
+    // $MARS holder fee discounts
+    pub mars_mint: Pubkey,
+    pub fee_tier_count: u8,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    pub creator_fee_bps: u16,
+    // Share of the platform fee paid to referrers
+    pub referral_share_bps: u16,
+    // Anti-sniper launch window
+    pub launch_window_secs: i64,
+    pub launch_max_buy_per_tx: u64,
+    pub launch_max_buy_per_wallet: u64,
+    pub launch_fee_bps: u16,
+    // Per-curve circuit breaker
+    pub circuit_breaker_bps: u16,
+    pub circuit_breaker_window_secs: i64,
+    pub circuit_breaker_cooloff_secs: i64,
+    // Emergency pause: bitmask of PAUSE_* families, plus a separate withdrawal switch
+    pub paused: u8,
+    pub withdrawals_paused: bool,
+    // Two-step admin rotation
+    pub pending_admin: Pubkey,
+    // Admin timelock; `action_count` seeds the next queued action
+    pub admin_delay_secs: i64,
+    pub action_count: u64,
+    // SPL quote mints new curves may launch with
+    pub quote_asset_count: u8,
+    pub quote_assets: [QuoteAsset; MAX_QUOTE_ASSETS],
+}
+
+/// Curve parameters for an allowlisted quote mint, all in its base units.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct QuoteAsset {
+    pub mint: Pubkey,
+    pub initial_virtual_quote: u64,
+    // Recorded on new curves; SPL-quoted curves do not graduate while the AMM
+    // pool is SOL-only (see `graduation_cap`)
+    pub graduation_threshold: u64,
+    pub launch_max_buy_per_tx: u64,
+    pub launch_max_buy_per_wallet: u64,
+    pub sabotage_cost: u64,
+}
+
+/// Discount on both trading fees for holders of at least `min_balance` $MARS.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub min_balance: u64,
+    pub discount_bps: u16,
 }
 
 #[account]
@@ -811,6 +5238,31 @@ pub struct BondingCurve {
     pub takeover_initiator: Pubkey,
     pub sabotage_penalty_bps: u16,
     pub sabotage_end_ts: i64,
+    // Set once reserves have moved into the AMM pool
+    pub migrated: bool,
+    pub created_at: i64,
+    // TWAP accumulator: sum of spot price (lamports per whole token) x seconds
+    pub price_cumulative: u128,
+    pub last_price_update: i64,
+    // Circuit breaker: spot price at the start of the current window
+    pub breaker_window_start: i64,
+    pub breaker_reference_price: u64,
+    pub trading_halted: bool,
+    pub halt_reason: HaltReason,
+    pub halted_until: i64,
+    // Quote asset; `Pubkey::default()` for native SOL. For SPL-quoted curves the
+    // `*_sol` fields hold amounts of the quote token.
+    pub quote_mint: Pubkey,
+    pub graduation_threshold: u64,
+}
+
+#[account]
+pub struct AmmPool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub bump: u8,
 }
 
 #[account]
@@ -824,6 +5276,176 @@ pub struct VestingAccount {
     pub bump: u8,
 }
 
+/// Holds creator fees (as lamports) until the curve's creator claims them.
+#[account]
+pub struct CreatorFeeVault {
+    pub mint: Pubkey,
+    pub pending: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+/// Yield fees of one business mint, shared among its stakers through a
+/// reward-per-token index. Amounts are in the curve's quote asset.
+#[account]
+pub struct YieldVault {
+    pub mint: Pubkey,
+    pub total_staked: u64,
+    // Sum of the stakes weighted by their lock multipliers
+    pub total_weight: u64,
+    // Cumulative yield per unit of weight, scaled by `math::REWARD_INDEX_SCALE`
+    pub reward_index: u128,
+    // Yield that arrived while nothing was staked, streamed to stakers over
+    // `math::YIELD_STREAM_SECS`
+    pub undistributed: u64,
+    pub total_accrued: u64,
+    pub total_claimed: u64,
+    pub stream_updated_at: i64,
+    pub bump: u8,
+}
+
+/// A holder's stake in a yield vault, checkpointed at every change. The whole
+/// position shares one lock; while it runs, the stake earns (and votes) with
+/// the lock's multiplier.
+#[account]
+pub struct YieldPosition {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked: u64,
+    // `staked` times `multiplier_bps`, the position's share of the vault
+    pub weight: u64,
+    pub lock_end: i64,
+    pub multiplier_bps: u16,
+    // Index into `STAKE_LOCK_TIERS` of the running lock; 0 once it has lapsed
+    pub lock_tier: u8,
+    pub reward_index_paid: u128,
+    pub pending: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+/// Lamports a wallet has spent buying a curve during its launch window.
+/// Closed by the buyer once the window is over.
+#[account]
+pub struct LaunchAllocation {
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub bought: u64,
+    pub bump: u8,
+}
+
+/// Referral earnings (held as lamports) and attribution counters for one referrer.
+#[account]
+pub struct ReferrerAccount {
+    pub owner: Pubkey,
+    pub pending: u64,
+    pub total_earned: u64,
+    pub total_volume: u64,
+    pub trades: u64,
+    pub launches: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub price_cumulative: u128,
+}
+
+/// Ring buffer of cumulative-price checkpoints for one curve. `head` is the
+/// newest entry.
+#[account]
+pub struct PriceObservations {
+    pub mint: Pubkey,
+    pub head: u16,
+    pub count: u16,
+    pub observations: [Observation; MAX_OBSERVATIONS],
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderTrigger {
+    AtOrBelow,
+    AtOrAbove,
+}
+
+/// Conditional buy or sell escrowed until a keeper fills it. `amount` is
+/// lamports for buys and tokens for sells; `trigger_price` is in lamports per
+/// whole token.
+#[account]
+pub struct Order {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub trigger: OrderTrigger,
+    pub trigger_price: u64,
+    pub amount: u64,
+    pub min_out: u64,
+    pub keeper_tip: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Admin change subject to the timelock.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminActionKind {
+    UpdateConfig { platform_fee_bps: u16, yield_fee_bps: u16, yield_distributor: Pubkey },
+    ProposeAdmin { new_admin: Pubkey },
+    SetCircuitBreaker { threshold_bps: u16, window_secs: i64, cooloff_secs: i64 },
+    SetLaunchProtection { window_secs: i64, max_buy_per_tx: u64, max_buy_per_wallet: u64, launch_fee_bps: u16 },
+    SetReferralShare { referral_share_bps: u16 },
+    SetCreatorFee { creator_fee_bps: u16 },
+    SetFeeTiers { mars_mint: Pubkey, tiers: Vec<FeeTier> },
+    SetAdminDelay { delay_secs: i64 },
+    SeizeLockedTokens { vesting: Pubkey, new_owner: Pubkey },
+    // Appended so the Borsh tags of queued actions stay stable
+    SetQuoteAsset { asset: QuoteAsset },
+    RemoveQuoteAsset { mint: Pubkey },
+    OverrideMarket { market: Pubkey, outcome: bool },
+}
+
+#[account]
+pub struct AdminAction {
+    pub id: u64,
+    pub action: AdminActionKind,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Multisig {
+    pub creator: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    // Bumped whenever the signer set changes, invalidating pending proposals
+    pub owner_set_seqno: u32,
+    pub signer_bump: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Instruction awaiting multisig approval; `approvals` is indexed like `signers`.
+#[account]
+pub struct MultisigProposal {
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+    pub approvals: Vec<bool>,
+    pub owner_set_seqno: u32,
+    pub bump: u8,
+}
+
 #[account]
 pub struct Market {
     pub id: u64,
@@ -837,6 +5459,33 @@ pub struct Market {
     pub oracle: Pubkey,
 }
 
+/// Return data of `quote_swap`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SwapQuoteResult {
+    pub amount_in: u64,
+    pub tokens_out: u64,
+    pub sol_out: u64,
+    pub platform_fee: u64,
+    pub yield_fee: u64,
+    pub creator_fee: u64,
+    pub penalty: u64,
+    pub refund: u64,
+    // Withheld by a Token-2022 mint; included in `amount_in` of a sell and
+    // already taken out of `tokens_out` of a buy
+    pub token_transfer_fee: u64,
+    pub fee_tier: u8,
+    pub price_impact_bps: u64,
+    pub virtual_sol: u128,
+    pub virtual_tokens: u128,
+    pub real_sol: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HaltReason {
+    None,
+    PriceMove,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum Sector {
     Tech,
@@ -855,6 +5504,158 @@ pub struct BusinessLaunched {
     pub name: String,
     pub symbol: String,
     pub creator: Pubkey,
+    pub referrer: Option<Pubkey>,
+    // `Pubkey::default()` for native SOL
+    pub quote_mint: Pubkey,
+    // Filled amount of the pre-buy, after any cap at graduation
+    pub initial_buy_lamports: u64,
+    pub initial_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BusinessMetadataUpdated {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BusinessToBusinessSwap {
+    pub trader: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub tokens_in: u64,
+    pub sol_routed: u64,
+    pub sol_refunded: u64,
+    pub tokens_out: u64,
+    pub total_fees: u64,
+    pub timestamp: i64,
+}
+
+/// One curve trade. `amount_in` is lamports on a buy and tokens on a sell;
+/// `penalty` is the sabotage penalty withheld from the output. Reserves are
+/// after the trade.
+#[event]
+pub struct TradeExecuted {
+    pub trader: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub platform_fee: u64,
+    pub yield_fee: u64,
+    pub creator_fee: u64,
+    pub penalty: u64,
+    pub fee_tier: u8,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub virtual_sol: u128,
+    pub virtual_tokens: u128,
+    pub real_sol: u64,
+    // Traded against the graduated AMM pool, whose reserves are reported as
+    // `virtual_sol` / `virtual_tokens`, rather than the bonding curve
+    pub pool: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub platform_fee_bps: u16,
+    pub yield_fee_bps: u16,
+    pub yield_distributor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionQueued {
+    pub id: u64,
+    pub action: AdminActionKind,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionCancelled {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionExecuted {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub multisig_signer: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigSignersChanged {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub multisig: Pubkey,
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub paused: u8,
+    pub withdrawals_paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingHalted {
+    pub mint: Pubkey,
+    pub reason: HaltReason,
+    pub reference_price: u64,
+    pub price: u64,
+    pub move_bps: u64,
+    pub halted_until: i64,
     pub timestamp: i64,
 }
 
@@ -866,11 +5667,132 @@ pub struct PriceUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct FeeTierApplied {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub fee_tier: u8,
+    pub platform_fee: u64,
+    pub yield_fee: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct GraduationReady {
     pub mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
+    pub virtual_sol: u128,
+    pub virtual_tokens: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub lp_locked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityChanged {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub is_deposit: bool,
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldStakeChanged {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub staked: u64,
+    pub weight: u64,
+    pub lock_end: i64,
+    pub total_staked: u64,
+    pub total_weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldRewardsDeposited {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldClaimed {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeeAccrued {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub volume: u64,
+    pub referral_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub trigger: OrderTrigger,
+    pub trigger_price: u64,
+    pub amount: u64,
+    pub min_out: u64,
+    pub keeper_tip: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderExecuted {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub keeper: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub trigger_price: u64,
+    pub spot_price: u64,
+    pub keeper_tip: u64,
     pub timestamp: i64,
 }
 
@@ -878,6 +5800,7 @@ pub struct GraduationReady {
 pub struct TakeoverInitiated {
     pub mint: Pubkey,
     pub initiator: Pubkey,
+    // Wallet balance plus lock-weighted yield stake
     pub stake: u64,
     pub timestamp: i64,
 }
@@ -890,6 +5813,16 @@ pub struct SabotageEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MarketOverridden {
+    pub market: Pubkey,
+    pub id: u64,
+    // `None` if the oracle had not resolved the market yet
+    pub previous_result: Option<bool>,
+    pub outcome: bool,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AssetsSeized {
     pub mint: Pubkey,
@@ -906,7 +5839,7 @@ pub struct AssetsSeized {
 pub enum UnifiedError {
     #[msg("Slippage tolerance exceeded")]
     SlippageExceeded,
-    #[msg("Already graduated to Raydium")]
+    #[msg("Curve has graduated; trade on its AMM pool")]
     AlreadyGraduated,
     #[msg("Unauthorized access")]
     Unauthorized,
@@ -924,4 +5857,62 @@ pub enum UnifiedError {
     TakeoverInProgress,
     #[msg("Market already resolved")]
     AlreadyResolved,
+    #[msg("Requested output exceeds curve liquidity")]
+    InsufficientLiquidity,
+    #[msg("Curve has not graduated yet")]
+    NotGraduated,
+    #[msg("Curve reserves already migrated to the AMM pool")]
+    AlreadyMigrated,
+    #[msg("Fee tier account is not the trader's $MARS account")]
+    InvalidFeeTierAccount,
+    #[msg("Fee exceeds the configured cap")]
+    FeeTooHigh,
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    #[msg("Buy exceeds the launch window limit")]
+    LaunchBuyLimitExceeded,
+    #[msg("Launch allocation account required during the launch window")]
+    LaunchAllocationRequired,
+    #[msg("Launch window is still active")]
+    LaunchWindowActive,
+    #[msg("Transaction landed after its deadline")]
+    DeadlineExceeded,
+    #[msg("Price impact exceeds the allowed maximum")]
+    PriceImpactExceeded,
+    #[msg("Mint is not supported by this instruction")]
+    UnsupportedMint,
+    #[msg("Order trigger price has not been reached")]
+    OrderNotTriggered,
+    #[msg("Not enough price observations to cover the TWAP window")]
+    InsufficientPriceHistory,
+    #[msg("Trading on this curve is halted")]
+    TradingHalted,
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Admin timelock is active; queue this action instead")]
+    TimelockRequired,
+    #[msg("Queued admin action is not executable yet")]
+    TimelockNotExpired,
+    #[msg("Signer is not a member of this multisig")]
+    NotMultisigSigner,
+    #[msg("Proposal predates a change of multisig signers")]
+    StaleProposal,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Stake is still locked")]
+    StakeLocked,
+    #[msg("Payout would leave the account below rent exemption")]
+    NotRentExempt,
+    #[msg("Deposit into a running lock must use a tier with at least its multiplier")]
+    LockTierDowngrade,
+}
+
+impl From<MathError> for UnifiedError {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::Overflow => UnifiedError::Overflow,
+            MathError::InvalidInput => UnifiedError::InvalidInput,
+            MathError::InsufficientLiquidity => UnifiedError::InsufficientLiquidity,
+        }
+    }
 }
@@ -0,0 +1,188 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { MarscorpUnified } from "../target/types/marscorp_exchange";
+
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+anchor.setProvider(anchor.AnchorProvider.env());
+export const provider = anchor.getProvider() as anchor.AnchorProvider;
+export const program = anchor.workspace
+  .MarscorpUnified as Program<MarscorpUnified>;
+export const admin = provider.wallet.publicKey;
+
+export const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+  PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+export const configPda = pda(Buffer.from("config"));
+
+export async function airdrop(to: PublicKey, sol: number) {
+  const sig = await provider.connection.requestAirdrop(
+    to,
+    sol * LAMPORTS_PER_SOL
+  );
+  const latest = await provider.connection.getLatestBlockhash();
+  await provider.connection.confirmTransaction({ signature: sig, ...latest });
+}
+
+export async function fundedKeypair(sol: number) {
+  const keypair = Keypair.generate();
+  await airdrop(keypair.publicKey, sol);
+  return keypair;
+}
+
+export const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+export async function tokenBalance(account: PublicKey) {
+  const { value } = await provider.connection.getTokenAccountBalance(account);
+  return new BN(value.amount);
+}
+
+/** Initializes the global config once per validator, with 1% + 1% fees. */
+export async function ensureConfig() {
+  const existing = await program.account.globalConfig.fetchNullable(configPda);
+  if (!existing) {
+    await program.methods
+      .initializeConfig(100, 100)
+      .accountsPartial({
+        config: configPda,
+        admin,
+        yieldDistributor: Keypair.generate().publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return program.account.globalConfig.fetch(configPda);
+}
+
+export interface Business {
+  mint: PublicKey;
+  curve: PublicKey;
+  vesting: PublicKey;
+  creatorFeeVault: PublicKey;
+  yieldVault: PublicKey;
+  curveTokenVault: PublicKey;
+  vestingTokenVault: PublicKey;
+  metadata: PublicKey;
+}
+
+export function businessAccounts(creator: PublicKey, symbol: string): Business {
+  const mint = pda(Buffer.from("mint"), creator.toBuffer(), Buffer.from(symbol));
+  const curve = pda(Buffer.from("curve"), mint.toBuffer());
+  return {
+    mint,
+    curve,
+    vesting: pda(Buffer.from("vesting"), mint.toBuffer()),
+    creatorFeeVault: pda(Buffer.from("creator_vault"), mint.toBuffer()),
+    yieldVault: pda(Buffer.from("yield_vault"), mint.toBuffer()),
+    curveTokenVault: getAssociatedTokenAddressSync(mint, curve, true),
+    vestingTokenVault: pda(Buffer.from("vesting_vault"), mint.toBuffer()),
+    metadata: PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0],
+  };
+}
+
+/** Launches a SOL-quoted business through `create_business`, without a pre-buy. */
+export async function launchBusiness(
+  creator: Keypair,
+  symbol: string,
+  uri = `https://marscorp.example/${symbol}.json`
+) {
+  const business = businessAccounts(creator.publicKey, symbol);
+  await program.methods
+    .createBusiness(`${symbol} Corp`, symbol, uri, { tech: {} }, null, new BN(0))
+    .accountsPartial({
+      config: configPda,
+      ...business,
+      referrerAccount: null,
+      creatorTokenAccount: null,
+      launchAllocation: null,
+      quoteMint: null,
+      adminTreasury: admin,
+      creator: creator.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .preInstructions([
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+    ])
+    .signers([creator])
+    .rpc();
+  return business;
+}
+
+/** Trades on a bonding curve with `swap`, creating the trader's token account. */
+export async function swap(
+  user: Keypair,
+  business: Business,
+  isBuy: boolean,
+  amount: BN,
+  minOut = new BN(0)
+) {
+  const userTokenAccount = getAssociatedTokenAddressSync(
+    business.mint,
+    user.publicKey
+  );
+  await program.methods
+    .swap(isBuy, amount, minOut, null, null)
+    .accountsPartial({
+      config: configPda,
+      curve: business.curve,
+      user: user.publicKey,
+      mint: business.mint,
+      userTokenAccount,
+      curveTokenVault: business.curveTokenVault,
+      adminTreasury: admin,
+      yieldVault: business.yieldVault,
+      creatorFeeVault: business.creatorFeeVault,
+      marsHolderAccount: null,
+      marsStakePosition: null,
+      referrerAccount: null,
+      launchAllocation: null,
+      priceObservations: null,
+      quoteMint: null,
+      userQuoteAccount: null,
+      curveQuoteVault: null,
+      adminQuoteAccount: null,
+      yieldQuoteAccount: null,
+      creatorFeeQuoteVault: null,
+      quoteTokenProgram: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .preInstructions([
+      createAssociatedTokenAccountIdempotentInstruction(
+        user.publicKey,
+        userTokenAccount,
+        user.publicKey,
+        business.mint
+      ),
+    ])
+    .signers([user])
+    .rpc();
+  return userTokenAccount;
+}
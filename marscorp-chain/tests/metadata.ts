@@ -0,0 +1,203 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getTokenMetadata,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  Business,
+  TOKEN_METADATA_PROGRAM_ID,
+  businessAccounts,
+  configPda,
+  ensureConfig,
+  fundedKeypair,
+  launchBusiness,
+  program,
+  provider,
+} from "./helpers";
+
+/** Reads the fields we set from a Metaplex metadata account. */
+async function readMetadata(metadata: PublicKey) {
+  const info = await provider.connection.getAccountInfo(metadata);
+  expect(info, "metadata account").to.not.equal(null);
+  expect(info!.owner.toBase58()).to.equal(TOKEN_METADATA_PROGRAM_ID.toBase58());
+
+  const data = info!.data;
+  // key (1), update authority (32), mint (32), then borsh strings padded with NULs
+  let offset = 1;
+  const updateAuthority = new PublicKey(data.subarray(offset, offset + 32));
+  offset += 64;
+  const readString = () => {
+    const len = data.readUInt32LE(offset);
+    offset += 4;
+    const value = data.subarray(offset, offset + len).toString("utf8");
+    offset += len;
+    return value.replace(/\0+$/, "");
+  };
+  return {
+    updateAuthority,
+    name: readString(),
+    symbol: readString(),
+    uri: readString(),
+  };
+}
+
+describe("business metadata", () => {
+  let business: Business;
+  let creator: Keypair;
+
+  before(async () => {
+    await ensureConfig();
+    creator = await fundedKeypair(10);
+    business = await launchBusiness(
+      creator,
+      "META",
+      "https://marscorp.example/meta-v1.json"
+    );
+  });
+
+  const update = (
+    signer: Keypair,
+    name: string,
+    symbol: string,
+    uri: string
+  ) =>
+    program.methods
+      .updateBusinessMetadata(name, symbol, uri)
+      .accountsPartial({
+        config: configPda,
+        curve: business.curve,
+        mint: business.mint,
+        metadata: business.metadata,
+        creator: signer.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  it("creates Metaplex metadata owned by the curve", async () => {
+    const metadata = await readMetadata(business.metadata);
+    expect(metadata.updateAuthority.toBase58()).to.equal(
+      business.curve.toBase58()
+    );
+    expect(metadata.name).to.equal("META Corp");
+    expect(metadata.symbol).to.equal("META");
+    expect(metadata.uri).to.equal("https://marscorp.example/meta-v1.json");
+  });
+
+  it("lets the creator update it", async () => {
+    await update(
+      creator,
+      "Meta Holdings",
+      "METAH",
+      "https://marscorp.example/meta-v2.json"
+    );
+
+    const metadata = await readMetadata(business.metadata);
+    expect(metadata.name).to.equal("Meta Holdings");
+    expect(metadata.symbol).to.equal("METAH");
+    expect(metadata.uri).to.equal("https://marscorp.example/meta-v2.json");
+  });
+
+  it("rejects updates from anyone else", async () => {
+    const stranger = await fundedKeypair(1);
+    let failed = false;
+    try {
+      await update(stranger, "Hijacked", "HJK", "https://evil.example/x.json");
+    } catch (err) {
+      failed = true;
+      expect(String(err)).to.match(/Unauthorized|ConstraintHasOne/);
+    }
+    expect(failed).to.equal(true);
+
+    const metadata = await readMetadata(business.metadata);
+    expect(metadata.name).to.equal("Meta Holdings");
+  });
+});
+
+describe("Token-2022 business metadata", () => {
+  let business: Business;
+  let creator: Keypair;
+
+  before(async () => {
+    await ensureConfig();
+    creator = await fundedKeypair(10);
+    const accounts = businessAccounts(creator.publicKey, "T22M");
+    business = {
+      ...accounts,
+      curveTokenVault: getAssociatedTokenAddressSync(
+        accounts.mint,
+        accounts.curve,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    };
+    await program.methods
+      .createBusiness2022(
+        "T22M Corp",
+        "T22M",
+        "https://marscorp.example/t22m-v1.json",
+        { tech: {} },
+        0
+      )
+      .accountsPartial({
+        config: configPda,
+        mint: business.mint,
+        curve: business.curve,
+        vesting: business.vesting,
+        creatorFeeVault: business.creatorFeeVault,
+        yieldVault: business.yieldVault,
+        curveTokenVault: business.curveTokenVault,
+        vestingTokenVault: business.vestingTokenVault,
+        referrerAccount: null,
+        quoteMint: null,
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("updates the metadata stored in the mint, growing it as needed", async () => {
+    await program.methods
+      .updateBusinessMetadata(
+        "T22M Interplanetary Holdings",
+        "T22MH",
+        "https://marscorp.example/t22m-interplanetary-holdings-v2.json"
+      )
+      .accountsPartial({
+        config: configPda,
+        curve: business.curve,
+        mint: business.mint,
+        metadata: null,
+        creator: creator.publicKey,
+        tokenMetadataProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const metadata = await getTokenMetadata(
+      provider.connection,
+      business.mint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(metadata!.updateAuthority!.toBase58()).to.equal(
+      business.curve.toBase58()
+    );
+    expect(metadata!.name).to.equal("T22M Interplanetary Holdings");
+    expect(metadata!.symbol).to.equal("T22MH");
+    expect(metadata!.uri).to.equal(
+      "https://marscorp.example/t22m-interplanetary-holdings-v2.json"
+    );
+  });
+});
@@ -0,0 +1,210 @@
+import { BN } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  Business,
+  admin,
+  balance,
+  configPda,
+  ensureConfig,
+  fundedKeypair,
+  launchBusiness,
+  pda,
+  program,
+  provider,
+  swap,
+  tokenBalance,
+} from "./helpers";
+
+const KEEPER_TIP = new BN(1_000_000);
+// Spot prices are in lamports per whole token, so every live curve is above this
+const ALWAYS = { trigger: { atOrAbove: {} }, price: new BN(1) };
+const NEVER = { trigger: { atOrBelow: {} }, price: new BN(1) };
+
+describe("limit orders", () => {
+  let business: Business;
+  let owner: Keypair;
+  let keeper: Keypair;
+  let ownerTokens: PublicKey;
+  let order: PublicKey;
+  let orderVault: PublicKey;
+
+  before(async () => {
+    await ensureConfig();
+    const creator = await fundedKeypair(10);
+    business = await launchBusiness(creator, "ORDR");
+    owner = await fundedKeypair(20);
+    keeper = await fundedKeypair(1);
+    ownerTokens = await swap(owner, business, true, new BN(LAMPORTS_PER_SOL));
+    order = pda(
+      Buffer.from("order"),
+      business.mint.toBuffer(),
+      owner.publicKey.toBuffer()
+    );
+    orderVault = pda(Buffer.from("order_vault"), order.toBuffer());
+  });
+
+  const place = (
+    isBuy: boolean,
+    when: typeof ALWAYS,
+    amount: BN,
+    minOut = new BN(0)
+  ) =>
+    program.methods
+      .placeOrder(isBuy, when.trigger, when.price, amount, minOut, KEEPER_TIP)
+      .accountsPartial({
+        config: configPda,
+        curve: business.curve,
+        mint: business.mint,
+        order,
+        orderVault: isBuy ? null : orderVault,
+        ownerTokenAccount: isBuy ? null : ownerTokens,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+  const execute = (
+    isBuy: boolean,
+    curveTokenVault = business.curveTokenVault
+  ) =>
+    program.methods
+      .executeOrder()
+      .accountsPartial({
+        config: configPda,
+        curve: business.curve,
+        mint: business.mint,
+        order,
+        orderVault: isBuy ? null : orderVault,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerTokens,
+        curveTokenVault,
+        adminTreasury: admin,
+        yieldVault: business.yieldVault,
+        creatorFeeVault: business.creatorFeeVault,
+        marsHolderAccount: null,
+        marsStakePosition: null,
+        keeper: keeper.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+
+  const cancel = (isBuy: boolean) =>
+    program.methods
+      .cancelOrder()
+      .accountsPartial({
+        config: configPda,
+        order,
+        mint: business.mint,
+        orderVault: isBuy ? null : orderVault,
+        ownerTokenAccount: isBuy ? null : ownerTokens,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([owner])
+      .rpc();
+
+  const orderClosed = async () =>
+    (await provider.connection.getAccountInfo(order)) === null;
+
+  it("executes a buy order out of the order PDA and tips the keeper", async () => {
+    await place(true, ALWAYS, new BN(LAMPORTS_PER_SOL / 2));
+    const tokensBefore = await tokenBalance(ownerTokens);
+    const keeperBefore = await balance(keeper.publicKey);
+
+    await execute(true);
+
+    expect((await tokenBalance(ownerTokens)).gt(tokensBefore)).to.equal(true);
+    // The tip outweighs the keeper's transaction fee
+    expect(await balance(keeper.publicKey)).to.be.greaterThan(keeperBefore);
+    expect(await orderClosed()).to.equal(true);
+  });
+
+  it("executes a sell order out of its escrow", async () => {
+    const tokensBefore = await tokenBalance(ownerTokens);
+    await place(false, ALWAYS, tokensBefore.divn(2));
+    expect((await tokenBalance(orderVault)).gtn(0)).to.equal(true);
+    const solBefore = await balance(owner.publicKey);
+
+    await execute(false);
+
+    expect((await tokenBalance(ownerTokens)).lt(tokensBefore)).to.equal(true);
+    expect(await balance(owner.publicKey)).to.be.greaterThan(solBefore);
+    expect(await orderClosed()).to.equal(true);
+  });
+
+  it("refuses to send a sell order's escrow anywhere but the curve vault", async () => {
+    await place(false, ALWAYS, (await tokenBalance(ownerTokens)).divn(4));
+    const keeperTokens = getAssociatedTokenAddressSync(
+      business.mint,
+      keeper.publicKey
+    );
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          keeper.publicKey,
+          keeperTokens,
+          keeper.publicKey,
+          business.mint
+        )
+      ),
+      [keeper]
+    );
+
+    let failed = false;
+    try {
+      await execute(false, keeperTokens);
+    } catch (err) {
+      failed = true;
+      expect(String(err)).to.match(/ConstraintAssociated|ConstraintTokenOwner/);
+    }
+    expect(failed).to.equal(true);
+    expect((await tokenBalance(keeperTokens)).isZero()).to.equal(true);
+
+    await cancel(false);
+  });
+
+  it("cancels an untriggered buy order and refunds escrow and tip", async () => {
+    await place(true, NEVER, new BN(LAMPORTS_PER_SOL / 2));
+    let failed = false;
+    try {
+      await execute(true);
+    } catch (err) {
+      failed = true;
+      expect(String(err)).to.match(/OrderNotTriggered/);
+    }
+    expect(failed).to.equal(true);
+
+    const solBefore = await balance(owner.publicKey);
+    await cancel(true);
+    expect(await balance(owner.publicKey)).to.be.greaterThan(
+      solBefore + LAMPORTS_PER_SOL / 2
+    );
+    expect(await orderClosed()).to.equal(true);
+  });
+
+  it("cancels an untriggered sell order and returns the escrowed tokens", async () => {
+    const tokensBefore = await tokenBalance(ownerTokens);
+    await place(false, NEVER, tokensBefore.divn(2));
+    expect((await tokenBalance(ownerTokens)).lt(tokensBefore)).to.equal(true);
+
+    await cancel(false);
+    expect((await tokenBalance(ownerTokens)).eq(tokensBefore)).to.equal(true);
+    expect(await orderClosed()).to.equal(true);
+  });
+});
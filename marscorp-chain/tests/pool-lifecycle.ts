@@ -0,0 +1,158 @@
+import { BN } from "@coral-xyz/anchor";
+import {
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  Business,
+  admin,
+  balance,
+  configPda,
+  ensureConfig,
+  fundedKeypair,
+  launchBusiness,
+  pda,
+  program,
+  swap,
+  tokenBalance,
+} from "./helpers";
+
+describe("graduation -> pool -> trading", () => {
+  let business: Business;
+  let pool: PublicKey;
+  let lpMint: PublicKey;
+  let lpLockVault: PublicKey;
+  let poolTokenVault: PublicKey;
+
+  before(async () => {
+    await ensureConfig();
+    const creator = await fundedKeypair(10);
+    business = await launchBusiness(creator, "POOL");
+    pool = pda(Buffer.from("pool"), business.mint.toBuffer());
+    lpMint = pda(Buffer.from("lp_mint"), business.mint.toBuffer());
+    lpLockVault = pda(Buffer.from("lp_lock"), business.mint.toBuffer());
+    poolTokenVault = getAssociatedTokenAddressSync(business.mint, pool, true);
+  });
+
+  it("runs migrate, a pool sell and an LP withdrawal end to end", async () => {
+    const trader = await fundedKeypair(200);
+
+    // A buy past the threshold is capped there and graduates the curve
+    const traderTokens = await swap(
+      trader,
+      business,
+      true,
+      new BN(100 * LAMPORTS_PER_SOL)
+    );
+    const graduated = await program.account.bondingCurve.fetch(business.curve);
+    expect(graduated.graduated).to.equal(true);
+
+    await program.methods
+      .migrateToPool()
+      .accountsPartial({
+        config: configPda,
+        curve: business.curve,
+        mint: business.mint,
+        curveTokenVault: business.curveTokenVault,
+        pool,
+        lpMint,
+        poolTokenVault,
+        lpLockVault,
+        payer: trader.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([trader])
+      .rpc();
+
+    const migrated = await program.account.bondingCurve.fetch(business.curve);
+    expect(migrated.migrated).to.equal(true);
+    const opened = await program.account.ammPool.fetch(pool);
+    expect(opened.solReserve.toString()).to.equal(
+      graduated.realSol.toString()
+    );
+
+    // Sell half the position into the pool; the SOL leg comes out of the pool PDA
+    const held = await tokenBalance(traderTokens);
+    const solBeforeSell = await balance(trader.publicKey);
+    await program.methods
+      .poolSwap(false, held.divn(2), new BN(1))
+      .accountsPartial({
+        config: configPda,
+        pool,
+        mint: business.mint,
+        user: trader.publicKey,
+        userTokenAccount: traderTokens,
+        poolTokenVault,
+        adminTreasury: admin,
+        yieldVault: business.yieldVault,
+        creatorFeeVault: business.creatorFeeVault,
+        marsHolderAccount: null,
+        marsStakePosition: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([trader])
+      .rpc();
+    expect(await balance(trader.publicKey)).to.be.greaterThan(solBeforeSell);
+    const afterSell = await program.account.ammPool.fetch(pool);
+    expect(afterSell.solReserve.lt(opened.solReserve)).to.equal(true);
+
+    // Provide liquidity, then withdraw all of it again
+    const traderLp = getAssociatedTokenAddressSync(lpMint, trader.publicKey);
+    const liquidityAccounts = {
+      config: configPda,
+      pool,
+      mint: business.mint,
+      lpMint,
+      poolTokenVault,
+      user: trader.publicKey,
+      userTokenAccount: traderTokens,
+      userLpAccount: traderLp,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+    await program.methods
+      .addLiquidity(
+        new BN(LAMPORTS_PER_SOL),
+        await tokenBalance(traderTokens),
+        new BN(1)
+      )
+      .accountsPartial(liquidityAccounts)
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          trader.publicKey,
+          traderLp,
+          trader.publicKey,
+          lpMint
+        ),
+      ])
+      .signers([trader])
+      .rpc();
+
+    const lp = await tokenBalance(traderLp);
+    expect(lp.gtn(0)).to.equal(true);
+    const solBeforeWithdraw = await balance(trader.publicKey);
+    await program.methods
+      .removeLiquidity(lp, new BN(1), new BN(1))
+      .accountsPartial(liquidityAccounts)
+      .signers([trader])
+      .rpc();
+
+    expect((await tokenBalance(traderLp)).isZero()).to.equal(true);
+    expect(await balance(trader.publicKey)).to.be.greaterThan(
+      solBeforeWithdraw
+    );
+  });
+});